@@ -141,7 +141,7 @@ pub struct FullType {
 #[serde(rename_all = "camelCase")]
 pub struct FullTypeFieldsArgs {
     #[serde(flatten)]
-    input_value: InputValue,
+    pub input_value: InputValue,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -201,6 +201,11 @@ pub struct InputValue {
     #[serde(rename = "type")]
     pub type_: InputValueType,
     pub default_value: Option<String>,
+    // Not in the responses of servers that predate deprecated arguments and input fields.
+    #[serde(default)]
+    pub is_deprecated: Option<bool>,
+    #[serde(default)]
+    pub deprecation_reason: Option<String>,
 }
 
 type InputValueType = TypeRef;
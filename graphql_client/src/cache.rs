@@ -0,0 +1,464 @@
+//! A persistent response cache, for command-line tools and batch jobs that repeatedly run the
+//! same queries.
+//!
+//! The cache stores the raw response bodies on disk, one file per operation and set of
+//! variables. It does not depend on a particular HTTP client: wrap the call to your transport in
+//! [`DiskCache::get_or_fetch`], or use [`DiskCache::get`] and [`DiskCache::put`] directly.
+//!
+//! ```no_run
+//! # use graphql_client::GraphQLQuery;
+//! # use std::time::Duration;
+//! # #[derive(GraphQLQuery)]
+//! # #[graphql(
+//! #   query_path = "../graphql_client_codegen/src/tests/star_wars_query.graphql",
+//! #   schema_path = "../graphql_client_codegen/src/tests/star_wars_schema.graphql"
+//! # )]
+//! # struct StarWarsQuery;
+//! # fn send(body: &graphql_client::QueryBody<star_wars_query::Variables>) -> Result<String, std::io::Error> { unimplemented!() }
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use graphql_client::cache::{DiskCache, FetchedResponse};
+//!
+//! let cache = DiskCache::new("/tmp/graphql-cache")
+//!     .with_default_ttl(Duration::from_secs(60))
+//!     .with_operation_ttl("StarWarsQuery", Duration::from_secs(3600));
+//!
+//! let variables = star_wars_query::Variables {
+//!     episode_for_hero: star_wars_query::Episode::NEWHOPE,
+//! };
+//!
+//! let response = cache.get_or_fetch::<StarWarsQuery, std::io::Error>(variables, |body| {
+//!     Ok(FetchedResponse {
+//!         body: send(body)?,
+//!         cache_control: None,
+//!     })
+//! })?;
+//!
+//! let hero = response.data.and_then(|data| data.hero);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{ClientError, GraphQLQuery, QueryBody, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A response cache backed by a directory on disk.
+///
+/// Entries are keyed by a hash of the operation (name and query document) and by the canonical
+/// JSON serialization of the variables. Responses that contain `errors` are never stored.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    directory: PathBuf,
+    default_ttl: Option<Duration>,
+    operation_ttls: HashMap<String, Duration>,
+}
+
+/// A response received from the server, to be stored in the cache.
+#[derive(Debug, Clone)]
+pub struct FetchedResponse {
+    /// The response body.
+    pub body: String,
+    /// The value of the `Cache-Control` header of the response, if any.
+    pub cache_control: Option<String>,
+}
+
+/// What is stored on disk for each cached response.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Seconds since the UNIX epoch.
+    expires_at: u64,
+    /// The canonical variables, to guard against hash collisions.
+    variables: String,
+    body: String,
+}
+
+impl DiskCache {
+    /// Create a cache storing its entries in `directory`. The directory is created on the first
+    /// write if it does not exist.
+    ///
+    /// Without a TTL, responses are only cached when the server allows it with a `max-age`
+    /// directive in the `Cache-Control` header.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        DiskCache {
+            directory: directory.into(),
+            default_ttl: None,
+            operation_ttls: HashMap::new(),
+        }
+    }
+
+    /// How long responses are kept, for operations without a specific TTL.
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// How long responses to the operation named `operation_name` are kept.
+    pub fn with_operation_ttl(mut self, operation_name: impl Into<String>, ttl: Duration) -> Self {
+        self.operation_ttls.insert(operation_name.into(), ttl);
+        self
+    }
+
+    /// The directory where the entries are stored.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Return the cached response body for this request, if there is one and it has not expired.
+    pub fn get<Variables: Serialize>(
+        &self,
+        query_body: &QueryBody<Variables>,
+    ) -> io::Result<Option<String>> {
+        self.get_at(query_body, unix_now())
+    }
+
+    fn get_at<Variables: Serialize>(
+        &self,
+        query_body: &QueryBody<Variables>,
+        now: u64,
+    ) -> io::Result<Option<String>> {
+        let variables = crate::canonical::canonical_variables(&query_body.variables)?;
+        let path = self.entry_path(query_body, &variables);
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let entry: CacheEntry = match serde_json::from_str(&contents) {
+            Ok(entry) => entry,
+            // A corrupted entry is a cache miss. It will be overwritten.
+            Err(_) => return Ok(None),
+        };
+
+        if entry.variables != variables {
+            return Ok(None);
+        }
+
+        if entry.expires_at <= now {
+            fs::remove_file(&path).ok();
+            return Ok(None);
+        }
+
+        Ok(Some(entry.body))
+    }
+
+    /// Store a response body for this request. Returns whether the response was stored.
+    ///
+    /// The response is not stored when it contains errors, when the server forbids it with a
+    /// `no-store` or `no-cache` directive, or when no TTL applies. A `max-age` directive can
+    /// shorten the configured TTL, but never extend it.
+    pub fn put<Variables: Serialize>(
+        &self,
+        query_body: &QueryBody<Variables>,
+        response: &FetchedResponse,
+    ) -> io::Result<bool> {
+        self.put_at(query_body, response, unix_now())
+    }
+
+    fn put_at<Variables: Serialize>(
+        &self,
+        query_body: &QueryBody<Variables>,
+        response: &FetchedResponse,
+        now: u64,
+    ) -> io::Result<bool> {
+        if response_has_errors(&response.body) {
+            return Ok(false);
+        }
+
+        let ttl = match self.ttl(query_body.operation_name, response.cache_control.as_deref()) {
+            Some(ttl) if ttl > Duration::from_secs(0) => ttl,
+            _ => return Ok(false),
+        };
+
+        let variables = crate::canonical::canonical_variables(&query_body.variables)?;
+        let path = self.entry_path(query_body, &variables);
+        let entry = CacheEntry {
+            expires_at: now.saturating_add(ttl.as_secs()),
+            variables,
+            body: response.body.clone(),
+        };
+
+        fs::create_dir_all(&self.directory)?;
+
+        // Write to a temporary file first, so concurrent readers never see a partial entry. Each
+        // write has its own temporary file, so concurrent writers of the same entry do not mix
+        // their contents.
+        static TMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let tmp_path = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp_path, serde_json::to_vec(&entry)?)?;
+        fs::rename(&tmp_path, &path)?;
+
+        Ok(true)
+    }
+
+    /// Return the cached response for this query if there is one, otherwise call `fetch` to
+    /// send the request and cache its response.
+    ///
+    /// Failing to read or write the cache is not an error: the cache is bypassed instead.
+    pub fn get_or_fetch<Q, E>(
+        &self,
+        variables: Q::Variables,
+        fetch: impl FnOnce(&QueryBody<Q::Variables>) -> Result<FetchedResponse, E>,
+    ) -> Result<Response<Q::ResponseData>, ClientError>
+    where
+        Q: GraphQLQuery,
+        E: Into<ClientError>,
+    {
+        let query_body = Q::build_query(variables);
+
+        if let Ok(Some(body)) = self.get(&query_body) {
+            if let Ok(response) = serde_json::from_str(&body) {
+                return Ok(response);
+            }
+        }
+
+        let fetched = fetch(&query_body).map_err(Into::into)?;
+        let response = Response::from_json(&fetched.body)?;
+
+        self.put(&query_body, &fetched).ok();
+
+        Ok(response)
+    }
+
+    /// The TTL for a response, taking into account the `Cache-Control` header.
+    fn ttl(&self, operation_name: &str, cache_control: Option<&str>) -> Option<Duration> {
+        let configured = self
+            .operation_ttls
+            .get(operation_name)
+            .copied()
+            .or(self.default_ttl);
+
+        let directives = cache_control.map(CacheControl::parse).unwrap_or_default();
+
+        if directives.no_store {
+            return None;
+        }
+
+        match (configured, directives.max_age) {
+            (Some(configured), Some(max_age)) => Some(configured.min(max_age)),
+            (configured, max_age) => configured.or(max_age),
+        }
+    }
+
+    fn entry_path(&self, query_body: &QueryBody<impl Serialize>, variables: &str) -> PathBuf {
+        let operation_hash = fnv1a(
+            query_body
+                .operation_name
+                .bytes()
+                .chain(std::iter::once(0))
+                .chain(query_body.query.bytes()),
+        );
+        let variables_hash = fnv1a(variables.bytes());
+
+        self.directory.join(format!(
+            "{}-{:016x}-{:016x}.json",
+            query_body.operation_name, operation_hash, variables_hash
+        ))
+    }
+}
+
+/// The `Cache-Control` directives the cache cares about.
+#[derive(Debug, Default, PartialEq)]
+struct CacheControl {
+    no_store: bool,
+    max_age: Option<Duration>,
+}
+
+impl CacheControl {
+    fn parse(header: &str) -> Self {
+        let mut directives = CacheControl::default();
+
+        for directive in header.split(',').map(str::trim) {
+            let mut parts = directive.splitn(2, '=');
+            let name = parts.next().unwrap_or_default().to_ascii_lowercase();
+            let value = parts.next().map(|value| value.trim().trim_matches('"'));
+
+            match (name.as_str(), value) {
+                // `no-cache` allows storing, but requires revalidation before each use, which this
+                // cache cannot do.
+                ("no-store", _) | ("no-cache", _) => directives.no_store = true,
+                ("max-age", Some(seconds)) => {
+                    directives.max_age = seconds.parse().ok().map(Duration::from_secs)
+                }
+                _ => (),
+            }
+        }
+
+        directives
+    }
+}
+
+fn response_has_errors(body: &str) -> bool {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(value) => match value.get("errors") {
+            None | Some(serde_json::Value::Null) => false,
+            Some(serde_json::Value::Array(errors)) => !errors.is_empty(),
+            Some(_) => true,
+        },
+        // Not a GraphQL response.
+        Err(_) => true,
+    }
+}
+
+/// 64-bit FNV-1a. Unlike the standard library hashers, it is guaranteed to be stable across
+/// runs and Rust versions, which matters for file names.
+fn fnv1a(bytes: impl Iterator<Item = u8>) -> u64 {
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_cache(name: &str) -> DiskCache {
+        let directory = std::env::temp_dir().join(format!(
+            "graphql-client-cache-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::remove_dir_all(&directory).ok();
+        DiskCache::new(directory)
+    }
+
+    fn query_body(episode: &str) -> QueryBody<serde_json::Value> {
+        QueryBody {
+            variables: json!({ "episode": episode, "first": 10 }),
+            query: "query Hero($episode: Episode, $first: Int) { hero { name } }",
+            operation_name: "Hero",
+        }
+    }
+
+    fn fetched(body: &str, cache_control: Option<&str>) -> FetchedResponse {
+        FetchedResponse {
+            body: body.to_owned(),
+            cache_control: cache_control.map(ToOwned::to_owned),
+        }
+    }
+
+    const RESPONSE: &str = r#"{"data": {"hero": {"name": "R2-D2"}}}"#;
+
+    #[test]
+    fn responses_are_cached_until_they_expire() {
+        let cache = test_cache("expiry").with_operation_ttl("Hero", Duration::from_secs(60));
+
+        assert!(cache
+            .put_at(&query_body("JEDI"), &fetched(RESPONSE, None), 1000)
+            .unwrap());
+
+        let reordered = QueryBody {
+            variables: json!({ "first": 10, "episode": "JEDI" }),
+            ..query_body("JEDI")
+        };
+        assert_eq!(
+            cache.get_at(&reordered, 1059).unwrap().as_deref(),
+            Some(RESPONSE)
+        );
+        assert_eq!(cache.get_at(&query_body("EMPIRE"), 1059).unwrap(), None);
+        assert_eq!(cache.get_at(&query_body("JEDI"), 1060).unwrap(), None);
+
+        fs::remove_dir_all(cache.directory()).ok();
+    }
+
+    #[test]
+    fn concurrent_puts_of_the_same_entry_do_not_mix() {
+        let cache = std::sync::Arc::new(
+            test_cache("concurrent").with_operation_ttl("Hero", Duration::from_secs(60)),
+        );
+        let responses: Vec<String> = (0..8)
+            .map(|idx| {
+                format!(
+                    r#"{{"data": {{"hero": {{"name": "{}"}}}}}}"#,
+                    "R2-D2".repeat(idx * 100)
+                )
+            })
+            .collect();
+
+        let threads: Vec<_> = responses
+            .iter()
+            .cloned()
+            .map(|response| {
+                let cache = cache.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        assert!(cache
+                            .put(&query_body("JEDI"), &fetched(&response, None))
+                            .unwrap());
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let cached = cache.get(&query_body("JEDI")).unwrap().unwrap();
+        assert!(responses.contains(&cached));
+        assert_eq!(fs::read_dir(cache.directory()).unwrap().count(), 1);
+
+        fs::remove_dir_all(cache.directory()).ok();
+    }
+
+    #[test]
+    fn responses_with_errors_are_not_cached() {
+        let cache = test_cache("errors").with_default_ttl(Duration::from_secs(60));
+        let response = r#"{"data": null, "errors": [{"message": "Too many requests"}]}"#;
+
+        assert!(!cache
+            .put(&query_body("JEDI"), &fetched(response, None))
+            .unwrap());
+        assert!(cache
+            .put(
+                &query_body("JEDI"),
+                &fetched(r#"{"data": {"hero": null}, "errors": []}"#, None)
+            )
+            .unwrap());
+
+        fs::remove_dir_all(cache.directory()).ok();
+    }
+
+    #[test]
+    fn cache_control_is_honoured() {
+        let cache = test_cache("cache-control").with_default_ttl(Duration::from_secs(600));
+
+        assert_eq!(
+            cache.ttl("Hero", Some("public, max-age=30")),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            cache.ttl("Hero", Some("max-age=3600")),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(cache.ttl("Hero", Some("no-store")), None);
+        assert_eq!(cache.ttl("Hero", Some("private, no-cache")), None);
+        assert_eq!(
+            DiskCache::new("unused").ttl("Hero", Some("max-age=5")),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(DiskCache::new("unused").ttl("Hero", None), None);
+
+        assert!(!cache
+            .put(&query_body("JEDI"), &fetched(RESPONSE, Some("max-age=0")))
+            .unwrap());
+    }
+}
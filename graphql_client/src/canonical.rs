@@ -0,0 +1,57 @@
+//! Canonical serialization of variables, used to identify identical requests.
+
+/// The variables serialized as JSON, with object keys sorted at every level, so two equal sets
+/// of variables always produce the same string.
+pub(crate) fn canonical_variables<Variables: serde::Serialize>(
+    variables: &Variables,
+) -> Result<String, serde_json::Error> {
+    Ok(canonicalize(serde_json::to_value(variables)?).to_string())
+}
+
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(object) => {
+            let mut entries: Vec<_> = object.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(canonicalize).collect())
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn canonical_variables_do_not_depend_on_key_order() {
+        let a = canonical_variables(&json!({
+            "episode": "JEDI",
+            "first": { "count": 3, "after": null },
+        }))
+        .unwrap();
+        let b = canonical_variables(&json!({
+            "first": { "after": null, "count": 3 },
+            "episode": "JEDI",
+        }))
+        .unwrap();
+        let c = canonical_variables(&json!({
+            "first": { "after": null, "count": 4 },
+            "episode": "JEDI",
+        }))
+        .unwrap();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
@@ -0,0 +1,228 @@
+//! The error type shared by all transports.
+//!
+//! The browser client, the response cache and custom transports all report their failures as a
+//! [`ClientError`], so application code does not depend on the transport it uses.
+
+use crate::stream::StreamError;
+use crate::{Error, Response};
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+/// All the ways a GraphQL request can fail, whatever the transport.
+///
+/// Transports map their failures into this type, so application code can handle them in one
+/// place. Failures that only make sense for one transport are wrapped in
+/// [`ClientError::Transport`], and can be recovered with `downcast_ref`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ClientError {
+    /// The request could not be sent, or the connection failed before a response was received.
+    Network(String),
+    /// The server responded with a body that is not a GraphQL response, for example an error page
+    /// from a proxy. See [`crate::http::is_graphql_response`].
+    Http {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The value of the `Content-Type` header of the response, if any.
+        content_type: Option<String>,
+        /// The response body.
+        body: String,
+    },
+    /// The response body does not match the shape expected by the generated code.
+    Decode(DecodeError),
+    /// The server executed the request, but returned errors. See [`Response::into_data`].
+    GraphQL(Vec<Error>),
+    /// A failure specific to a transport.
+    Transport(Arc<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+impl ClientError {
+    /// Wrap a transport-specific error.
+    pub fn transport(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        ClientError::Transport(Arc::new(err))
+    }
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Network(message) => write!(f, "Network error: {}", message),
+            ClientError::Http {
+                status,
+                content_type,
+                ..
+            } => write!(
+                f,
+                "HTTP error (status {}, content type: {})",
+                status,
+                content_type.as_deref().unwrap_or("none")
+            ),
+            ClientError::Decode(err) => Display::fmt(err, f),
+            ClientError::GraphQL(errors) => {
+                f.write_str("The server returned errors:")?;
+                for error in errors {
+                    write!(f, "\n  {}", error)?;
+                }
+                Ok(())
+            }
+            ClientError::Transport(err) => write!(f, "Transport error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Decode(err) => Some(err),
+            ClientError::Transport(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// I/O errors are raised by blocking transports, e.g. while reading a response body.
+impl From<std::io::Error> for ClientError {
+    fn from(err: std::io::Error) -> Self {
+        ClientError::transport(err)
+    }
+}
+
+impl<E: Into<ClientError>> From<StreamError<E>> for ClientError {
+    fn from(err: StreamError<E>) -> Self {
+        match err {
+            StreamError::Json(err) if err.is_io() => ClientError::Network(err.to_string()),
+            StreamError::Json(err) => ClientError::Decode(DecodeError {
+                path: String::new(),
+                line: err.line(),
+                column: err.column(),
+                message: err.to_string(),
+            }),
+            StreamError::Item(err) => err.into(),
+        }
+    }
+}
+
+impl From<DecodeError> for ClientError {
+    fn from(err: DecodeError) -> Self {
+        ClientError::Decode(err)
+    }
+}
+
+/// A response body that could not be deserialized, with the location of the problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeError {
+    /// The path to the value that could not be deserialized, e.g. `data.hero.friends[2].name`,
+    /// or an empty string if it is not known.
+    pub path: String,
+    /// The line in the response body (starting from 1), or 0 if unknown.
+    pub line: usize,
+    /// The column in the response body (starting from 1), or 0 if unknown.
+    pub column: usize,
+    /// The deserialization error message.
+    pub message: String,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Response decoding error at `{}`: {}",
+            self.path, self.message
+        )
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl<Data> Response<Data>
+where
+    Data: for<'de> serde::Deserialize<'de>,
+{
+    /// Deserialize a response body, reporting where deserialization failed.
+    pub fn from_json(body: &str) -> Result<Self, ClientError> {
+        let deserializer = &mut serde_json::Deserializer::from_str(body);
+
+        serde_path_to_error::deserialize(deserializer).map_err(|err| {
+            let path = err.path().to_string();
+            let inner = err.into_inner();
+
+            DecodeError {
+                path,
+                line: inner.line(),
+                column: inner.column(),
+                message: inner.to_string(),
+            }
+            .into()
+        })
+    }
+}
+
+impl<Data> Response<Data> {
+    /// The response data, or [`ClientError::GraphQL`] if the response contains errors.
+    ///
+    /// Partial data returned alongside errors is discarded. Match on the response directly to
+    /// keep it.
+    pub fn into_data(self) -> Result<Data, ClientError> {
+        match (self.data, self.errors) {
+            (_, Some(errors)) if !errors.is_empty() => Err(ClientError::GraphQL(errors)),
+            (Some(data), _) => Ok(data),
+            (None, _) => Err(ClientError::Decode(DecodeError {
+                path: "data".to_owned(),
+                line: 0,
+                column: 0,
+                message: "the response contains neither data nor errors".to_owned(),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Hero {
+        name: String,
+        height: f64,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct ResponseData {
+        heroes: Vec<Hero>,
+    }
+
+    #[test]
+    fn decode_errors_have_a_path_and_a_position() {
+        let body = "{\"data\": {\"heroes\": [\n{\"name\": \"Leia\", \"height\": 1.5},\n{\"name\": \"Luke\", \"height\": \"tall\"}\n]}}";
+
+        let err = match Response::<ResponseData>::from_json(body) {
+            Err(ClientError::Decode(err)) => err,
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        assert_eq!(err.path, "data.heroes[1].height");
+        assert_eq!(err.line, 3);
+        assert!(err.column > 0);
+    }
+
+    #[test]
+    fn into_data_separates_graphql_errors() {
+        let response: Response<ResponseData> =
+            Response::from_json(r#"{"data": null, "errors": [{"message": "Not authorized"}]}"#)
+                .unwrap();
+
+        match response.into_data() {
+            Err(ClientError::GraphQL(errors)) => assert_eq!(errors[0].message, "Not authorized"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let response: Response<ResponseData> =
+            Response::from_json(r#"{"data": {"heroes": []}, "errors": []}"#).unwrap();
+
+        assert_eq!(
+            response.into_data().unwrap(),
+            ResponseData { heroes: Vec::new() }
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Helpers for the [GraphQL over HTTP](https://graphql.github.io/graphql-over-http/draft/)
+//! specification, shared by the clients.
+
+/// The media type of GraphQL responses, as defined by the GraphQL over HTTP specification.
+pub const GRAPHQL_RESPONSE_MEDIA_TYPE: &str = "application/graphql-response+json";
+
+/// The legacy media type for GraphQL responses. Most servers still use it.
+pub const JSON_MEDIA_TYPE: &str = "application/json";
+
+/// The value clients send in the `Accept` header: GraphQL responses are preferred, legacy JSON
+/// responses are still accepted.
+pub const ACCEPT_HEADER: &str = "application/graphql-response+json, application/json;q=0.9";
+
+/// Whether a response body can be interpreted as a GraphQL response, given the status code and
+/// the `Content-Type` header of the response.
+///
+/// - `application/graphql-response+json` bodies are always GraphQL responses, whatever the
+///   status code.
+/// - `application/json` bodies (and bodies without a media type) are only trusted for successful
+///   (2xx) responses. The body of an error response with this media type is typically produced by
+///   a proxy or a framework, not by the GraphQL server.
+/// - Any other media type is not a GraphQL response.
+pub fn is_graphql_response(status: u16, content_type: Option<&str>) -> bool {
+    let is_success = (200..300).contains(&status);
+
+    match content_type.map(media_type_essence).as_deref() {
+        Some(GRAPHQL_RESPONSE_MEDIA_TYPE) => true,
+        Some(JSON_MEDIA_TYPE) | None => is_success,
+        Some(_) => false,
+    }
+}
+
+/// The media type without its parameters, lowercased (e.g. `application/json; charset=utf-8`
+/// becomes `application/json`).
+fn media_type_essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graphql_response_media_type_is_trusted_for_any_status() {
+        assert!(is_graphql_response(200, Some(GRAPHQL_RESPONSE_MEDIA_TYPE)));
+        assert!(is_graphql_response(
+            400,
+            Some("application/graphql-response+json; charset=utf-8")
+        ));
+        assert!(is_graphql_response(500, Some(GRAPHQL_RESPONSE_MEDIA_TYPE)));
+    }
+
+    #[test]
+    fn json_media_type_is_only_trusted_for_successful_responses() {
+        assert!(is_graphql_response(
+            200,
+            Some("Application/JSON; charset=utf-8")
+        ));
+        assert!(is_graphql_response(200, None));
+        assert!(!is_graphql_response(401, Some(JSON_MEDIA_TYPE)));
+        assert!(!is_graphql_response(502, None));
+    }
+
+    #[test]
+    fn other_media_types_are_not_graphql_responses() {
+        assert!(!is_graphql_response(200, Some("text/html")));
+        assert!(!is_graphql_response(500, Some("text/plain; charset=utf-8")));
+    }
+}
@@ -14,11 +14,36 @@ extern crate graphql_query_derive;
 #[doc(hidden)]
 pub use graphql_query_derive::*;
 
+/// The crates of the scalar presets, for the generated code. See the `scalar_presets` argument of
+/// the graphql attribute.
+#[doc(hidden)]
+pub mod __scalar_presets {
+    #[cfg(feature = "scalars-chrono")]
+    pub use chrono;
+    #[cfg(feature = "scalars-decimal")]
+    pub use rust_decimal;
+    pub use serde_json;
+    #[cfg(feature = "scalars-url")]
+    pub use url;
+    #[cfg(feature = "scalars-uuid")]
+    pub use uuid;
+}
+
 use serde::*;
 
+mod client_error;
+pub mod http;
+
+#[cfg(feature = "disk-cache")]
+pub mod cache;
+#[cfg(any(feature = "disk-cache", feature = "web"))]
+mod canonical;
+pub mod stream;
 #[cfg(feature = "web")]
 pub mod web;
 
+pub use client_error::{ClientError, DecodeError};
+
 use std::collections::HashMap;
 use std::fmt::{self, Display};
 
@@ -72,10 +97,48 @@ pub trait GraphQLQuery {
     /// The top-level shape of the response data (the `data` field in the GraphQL response). In practice this should be generated, since it is hard to write by hand without error.
     type ResponseData: for<'de> serde::Deserialize<'de>;
 
+    /// The type of the operation, when it is known. Generated implementations always set it.
+    ///
+    /// Clients use it to decide which operations are safe to deduplicate: only queries are, and operations of unknown type are treated like mutations.
+    const OPERATION_TYPE: Option<OperationType> = None;
+
     /// Produce a GraphQL query struct that can be JSON serialized and sent to a GraphQL API.
     fn build_query(variables: Self::Variables) -> QueryBody<Self::Variables>;
 }
 
+/// The three types of GraphQL operations. See [`GraphQLQuery::OPERATION_TYPE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationType {
+    /// A read-only fetch.
+    Query,
+    /// A write followed by a fetch.
+    Mutation,
+    /// A long-lived request that fetches data in response to source events.
+    Subscription,
+}
+
+/// The error of the `FromStr` implementation of the generated enums without an `Other(String)`
+/// variant, for strings that are not values of the enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownEnumValue {
+    /// The name of the enum in the schema.
+    pub enum_name: &'static str,
+    /// The string that could not be parsed.
+    pub value: String,
+}
+
+impl Display for UnknownEnumValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a value of the `{}` enum",
+            self.value, self.enum_name
+        )
+    }
+}
+
+impl std::error::Error for UnknownEnumValue {}
+
 /// The form in which queries are sent over HTTP in most implementations. This will be built using the [`GraphQLQuery`] trait normally.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QueryBody<Variables> {
@@ -83,8 +146,13 @@ pub struct QueryBody<Variables> {
     pub variables: Variables,
     /// The GraphQL query, as a string.
     pub query: &'static str,
-    /// The GraphQL operation name, as a string.
-    #[serde(rename = "operationName")]
+    /// The GraphQL operation name, as a string. It is empty for anonymous operations, and is then
+    /// not sent.
+    #[serde(
+        rename = "operationName",
+        default,
+        skip_serializing_if = "str::is_empty"
+    )]
     pub operation_name: &'static str,
 }
 
@@ -0,0 +1,328 @@
+//! Streaming deserialization of responses with very large lists.
+//!
+//! [`Response`](crate::Response) needs the whole response in memory. For bulk exports, use
+//! [`for_each_list_item`] instead: it reads the body incrementally and deserializes the elements
+//! of one list field one at a time, so memory use does not grow with the length of the list.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // This would be the generated struct for the list items, e.g. `export_query::ExportQueryUsersNodes`.
+//! #[derive(Deserialize)]
+//! struct UserNode {
+//!     login: String,
+//! }
+//!
+//! // Any `std::io::Read` works, for example a blocking HTTP response body.
+//! let body = r#"{"data": {"users": {"totalCount": 2, "nodes": [{"login": "ada"}, {"login": "grace"}]}}}"#;
+//!
+//! let mut logins = Vec::new();
+//! let errors = graphql_client::stream::for_each_list_item(
+//!     body.as_bytes(),
+//!     &["users", "nodes"],
+//!     |user: UserNode| -> Result<(), std::io::Error> {
+//!         logins.push(user.login);
+//!         Ok(())
+//!     },
+//! )?;
+//!
+//! assert_eq!(logins, vec!["ada", "grace"]);
+//! assert!(errors.is_none());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Error;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::fmt::{self, Display};
+use std::io::{BufReader, Read};
+use std::marker::PhantomData;
+
+/// The ways [`for_each_list_item`] can fail.
+#[derive(Debug)]
+pub enum StreamError<E> {
+    /// The body is not valid JSON, or a list item does not match the expected shape.
+    Json(serde_json::Error),
+    /// The callback returned an error. The rest of the body was not read.
+    Item(E),
+}
+
+impl<E: Display> Display for StreamError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Json(err) => write!(f, "Response shape error: {}", err),
+            StreamError::Item(err) => write!(f, "Error processing a list item: {}", err),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for StreamError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StreamError::Json(err) => Some(err),
+            StreamError::Item(err) => Some(err),
+        }
+    }
+}
+
+/// Read a GraphQL response from `reader`, and call `on_item` with each element of the list at
+/// `path` as soon as it is deserialized.
+///
+/// `path` is the list of response keys (aliases, if any) leading to the list field, starting
+/// below `data`. Use `Option<Item>` as the item type if the elements of the list are nullable.
+/// When a field on the path is `null`, `on_item` is never called.
+///
+/// Everything outside the path is skipped without being deserialized, except for the top-level
+/// `errors`, which are returned.
+///
+/// The reader is buffered internally, so a socket or a file can be passed directly.
+pub fn for_each_list_item<T, E, R, F>(
+    reader: R,
+    path: &[&str],
+    on_item: F,
+) -> Result<Option<Vec<Error>>, StreamError<E>>
+where
+    T: for<'de> de::Deserialize<'de>,
+    R: Read,
+    F: FnMut(T) -> Result<(), E>,
+{
+    let mut state = StreamState {
+        on_item,
+        item_error: None,
+        errors: None,
+        _item: PhantomData,
+    };
+
+    // The deserializer reads one byte at a time.
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+
+    let result = ResponseSeed {
+        path,
+        state: &mut state,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|()| deserializer.end());
+
+    match (result, state.item_error) {
+        (_, Some(item_error)) => Err(StreamError::Item(item_error)),
+        (Err(err), None) => Err(StreamError::Json(err)),
+        (Ok(()), None) => Ok(state.errors),
+    }
+}
+
+struct StreamState<T, E, F> {
+    on_item: F,
+    item_error: Option<E>,
+    errors: Option<Vec<Error>>,
+    _item: PhantomData<T>,
+}
+
+/// The top-level object: `data` and `errors`.
+struct ResponseSeed<'a, 'p, T, E, F> {
+    path: &'p [&'p str],
+    state: &'a mut StreamState<T, E, F>,
+}
+
+impl<'de, 'a, 'p, T, E, F> DeserializeSeed<'de> for ResponseSeed<'a, 'p, T, E, F>
+where
+    T: for<'d> de::Deserialize<'d>,
+    F: FnMut(T) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a, 'p, T, E, F> Visitor<'de> for ResponseSeed<'a, 'p, T, E, F>
+where
+    T: for<'d> de::Deserialize<'d>,
+    F: FnMut(T) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a GraphQL response")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "data" => map.next_value_seed(PathSeed {
+                    path: self.path,
+                    state: &mut *self.state,
+                })?,
+                "errors" => self.state.errors = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A nullable object on the path to the list, or the list itself once `path` is empty.
+struct PathSeed<'a, 'p, T, E, F> {
+    path: &'p [&'p str],
+    state: &'a mut StreamState<T, E, F>,
+}
+
+impl<'de, 'a, 'p, T, E, F> DeserializeSeed<'de> for PathSeed<'a, 'p, T, E, F>
+where
+    T: for<'d> de::Deserialize<'d>,
+    F: FnMut(T) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de, 'a, 'p, T, E, F> Visitor<'de> for PathSeed<'a, 'p, T, E, F>
+where
+    T: for<'d> de::Deserialize<'d>,
+    F: FnMut(T) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.first() {
+            Some(key) => write!(f, "null or an object with a `{}` field", key),
+            None => f.write_str("null or a list"),
+        }
+    }
+
+    fn visit_none<Err: de::Error>(self) -> Result<(), Err> {
+        Ok(())
+    }
+
+    fn visit_unit<Err: de::Error>(self) -> Result<(), Err> {
+        Ok(())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        if self.path.is_empty() {
+            deserializer.deserialize_seq(self)
+        } else {
+            deserializer.deserialize_map(self)
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let (next, rest) = self.path.split_first().expect("visit_map on the list");
+
+        while let Some(key) = map.next_key::<String>()? {
+            if key == *next {
+                map.next_value_seed(PathSeed {
+                    path: rest,
+                    state: &mut *self.state,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(item) = seq.next_element::<T>()? {
+            if let Err(err) = (self.state.on_item)(item) {
+                self.state.item_error = Some(err);
+                return Err(de::Error::custom("the list item callback failed"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Node {
+        id: i32,
+    }
+
+    fn collect(body: &str, path: &[&str]) -> (Vec<Node>, Option<Vec<Error>>) {
+        let mut nodes = Vec::new();
+        let errors = for_each_list_item(body.as_bytes(), path, |node| -> Result<(), ()> {
+            nodes.push(node);
+            Ok(())
+        })
+        .unwrap();
+
+        (nodes, errors)
+    }
+
+    #[test]
+    fn items_are_streamed_and_the_rest_is_skipped() {
+        let body = r#"{
+            "errors": [{"message": "partial failure"}],
+            "data": {
+                "other": [{"id": 0}],
+                "export": {
+                    "pageInfo": {"hasNextPage": false},
+                    "nodes": [{"id": 1, "extra": true}, {"id": 2}]
+                }
+            },
+            "extensions": {"cost": 12}
+        }"#;
+
+        let (nodes, errors) = collect(body, &["export", "nodes"]);
+
+        assert_eq!(nodes, vec![Node { id: 1 }, Node { id: 2 }]);
+        assert_eq!(errors.unwrap()[0].message, "partial failure");
+    }
+
+    #[test]
+    fn null_on_the_path_yields_no_items() {
+        let (nodes, errors) = collect(r#"{"data": {"export": null}}"#, &["export", "nodes"]);
+        assert!(nodes.is_empty());
+        assert!(errors.is_none());
+
+        let (nodes, _) = collect(
+            r#"{"data": null, "errors": [{"message": "denied"}]}"#,
+            &["export", "nodes"],
+        );
+        assert!(nodes.is_empty());
+    }
+
+    #[test]
+    fn callback_errors_stop_the_stream() {
+        let body = r#"{"data": {"nodes": [{"id": 1}, {"id": 2}, {"id": 3}]}}"#;
+        let mut seen = Vec::new();
+
+        let result = for_each_list_item(body.as_bytes(), &["nodes"], |node: Node| {
+            if node.id == 2 {
+                return Err("disk full");
+            }
+            seen.push(node.id);
+            Ok(())
+        });
+
+        match result {
+            Err(StreamError::Item(err)) => assert_eq!(err, "disk full"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(seen, vec![1]);
+    }
+
+    #[test]
+    fn mismatched_items_are_json_errors() {
+        let body = r#"{"data": {"nodes": [{"id": "not a number"}]}}"#;
+
+        let result = for_each_list_item(body.as_bytes(), &["nodes"], |_: Node| -> Result<(), ()> {
+            Ok(())
+        });
+
+        assert!(matches!(result, Err(StreamError::Json(_))));
+    }
+}
@@ -1,9 +1,12 @@
 //! Use graphql_client inside browsers with
 //! [wasm-bindgen](https://github.com/rustwasm/wasm-bindgen).
 
+pub use crate::ClientError;
+
 use crate::*;
+use futures_util::future::{FutureExt, LocalBoxFuture, Shared};
 use log::*;
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use thiserror::*;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
@@ -18,19 +21,19 @@ use wasm_bindgen_futures::JsFuture;
 pub struct Client {
     endpoint: String,
     headers: HashMap<String, String>,
+    deduplicate_queries: bool,
+    in_flight: Rc<RefCell<HashMap<String, SharedFetch>>>,
 }
 
-/// All the ways a request can go wrong.
-///
-/// not exhaustive
-#[derive(Debug, Error, PartialEq)]
-pub enum ClientError {
+type SharedFetch = Shared<LocalBoxFuture<'static, Result<RawResponse, ClientError>>>;
+
+/// The failures specific to the browser client. They are reported as
+/// [`ClientError::Transport`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum WebError {
     /// The body couldn't be built
     #[error("Request body is not a valid string")]
     Body,
-    /// An error caused by window.fetch
-    #[error("Network error")]
-    Network(String),
     /// Error in a dynamic JS cast that should have worked
     #[error("JS casting error")]
     Cast,
@@ -39,9 +42,6 @@ pub enum ClientError {
         "No Window object available - the client works only in a browser (non-worker) context"
     )]
     NoWindow,
-    /// Response shape does not match the generated code
-    #[error("Response shape error")]
-    ResponseShape,
     /// Response could not be converted to text
     #[error("Response conversion to text failed (Response.text threw)")]
     ResponseText,
@@ -53,6 +53,12 @@ pub enum ClientError {
     JsException,
 }
 
+impl From<WebError> for ClientError {
+    fn from(err: WebError) -> Self {
+        ClientError::transport(err)
+    }
+}
+
 impl Client {
     /// Initialize a client. The `endpoint` parameter is the URI of the GraphQL API.
     pub fn new<Endpoint>(endpoint: Endpoint) -> Client
@@ -62,6 +68,8 @@ impl Client {
         Client {
             endpoint: endpoint.into(),
             headers: HashMap::new(),
+            deduplicate_queries: true,
+            in_flight: Rc::new(RefCell::new(HashMap::new())),
         }
     }
 
@@ -70,6 +78,15 @@ impl Client {
         self.headers.insert(name.into(), value.into());
     }
 
+    /// Whether concurrent calls of the same query with the same variables should share a single
+    /// network request. Enabled by default.
+    ///
+    /// Only queries are deduplicated: mutations, subscriptions and operations of unknown type
+    /// always send their own request.
+    pub fn set_deduplicate_queries(&mut self, deduplicate_queries: bool) {
+        self.deduplicate_queries = deduplicate_queries;
+    }
+
     /// Perform a query.
     ///
     // Lint disabled: We can pass by value because it's always an empty struct.
@@ -79,52 +96,156 @@ impl Client {
         _query: Q,
         variables: Q::Variables,
     ) -> Result<crate::Response<Q::ResponseData>, ClientError> {
-        let window = web_sys::window().ok_or(ClientError::NoWindow)?;
-        let body =
-            serde_json::to_string(&Q::build_query(variables)).map_err(|_| ClientError::Body)?;
+        let query_body = Q::build_query(variables);
+        let body = serde_json::to_string(&query_body).map_err(|_| WebError::Body)?;
+
+        let deduplication_key =
+            if self.deduplicate_queries && Q::OPERATION_TYPE == Some(OperationType::Query) {
+                Some(deduplication_key(&query_body).map_err(|_| WebError::Body)?)
+            } else {
+                None
+            };
+
+        let raw_response = match deduplication_key {
+            Some(key) => self.fetch_deduplicated(key, &body).await?,
+            None => fetch(self.build_request(&body)?).await?,
+        };
+
+        parse_response(raw_response)
+    }
+
+    /// Send the request, or wait for the identical request that is already in flight.
+    async fn fetch_deduplicated(
+        &self,
+        key: String,
+        body: &str,
+    ) -> Result<RawResponse, ClientError> {
+        let existing = self.in_flight.borrow().get(&key).cloned();
 
+        let shared = match existing {
+            Some(shared) => shared,
+            None => {
+                let shared = fetch(self.build_request(body)?).boxed_local().shared();
+                self.in_flight
+                    .borrow_mut()
+                    .insert(key.clone(), shared.clone());
+                shared
+            }
+        };
+
+        let result = shared.clone().await;
+
+        // The first waiter to wake up removes the entry, unless a new request with the same key
+        // was started in the meantime.
+        let mut in_flight = self.in_flight.borrow_mut();
+        if in_flight
+            .get(&key)
+            .map(|current| current.ptr_eq(&shared))
+            .unwrap_or(false)
+        {
+            in_flight.remove(&key);
+        }
+
+        result
+    }
+
+    fn build_request(&self, body: &str) -> Result<web_sys::Request, ClientError> {
         let mut request_init = web_sys::RequestInit::new();
         request_init
             .method("POST")
-            .body(Some(&JsValue::from_str(&body)));
+            .body(Some(&JsValue::from_str(body)));
 
         let request = web_sys::Request::new_with_str_and_init(&self.endpoint, &request_init)
-            .map_err(|_| ClientError::JsException)?;
+            .map_err(|_| WebError::JsException)?;
 
         let headers = request.headers();
         headers
             .set("Content-Type", "application/json")
-            .map_err(|_| ClientError::RequestError)?;
+            .map_err(|_| WebError::RequestError)?;
         headers
-            .set("Accept", "application/json")
-            .map_err(|_| ClientError::RequestError)?;
+            .set("Accept", crate::http::ACCEPT_HEADER)
+            .map_err(|_| WebError::RequestError)?;
         for (header_name, header_value) in self.headers.iter() {
             headers
                 .set(header_name, header_value)
-                .map_err(|_| ClientError::RequestError)?;
+                .map_err(|_| WebError::RequestError)?;
         }
 
-        let res = JsFuture::from(window.fetch_with_request(&request))
-            .await
-            .map_err(|err| ClientError::Network(js_sys::Error::from(err).message().into()))?;
-        debug!("response: {:?}", res);
-        let cast_response = res
-            .dyn_into::<web_sys::Response>()
-            .map_err(|_| ClientError::Cast)?;
-
-        let text_promise = cast_response
-            .text()
-            .map_err(|_| ClientError::ResponseText)?;
-        let text = JsFuture::from(text_promise)
-            .await
-            .map_err(|_| ClientError::ResponseText)?;
-
-        let response_text = text.as_string().unwrap_or_default();
-        debug!("response text as string: {:?}", response_text);
-        let response_data =
-            serde_json::from_str(&response_text).map_err(|_| ClientError::ResponseShape)?;
-        Ok(response_data)
+        Ok(request)
+    }
+}
+
+/// A response, before its body is interpreted.
+#[derive(Debug, Clone)]
+struct RawResponse {
+    status: u16,
+    content_type: Option<String>,
+    body: String,
+}
+
+async fn fetch(request: web_sys::Request) -> Result<RawResponse, ClientError> {
+    let window = web_sys::window().ok_or(WebError::NoWindow)?;
+
+    let res = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|err| ClientError::Network(js_sys::Error::from(err).message().into()))?;
+    debug!("response: {:?}", res);
+    let cast_response = res
+        .dyn_into::<web_sys::Response>()
+        .map_err(|_| WebError::Cast)?;
+    let status = cast_response.status();
+    let content_type = cast_response
+        .headers()
+        .get("Content-Type")
+        .map_err(|_| WebError::JsException)?;
+
+    let text_promise = cast_response.text().map_err(|_| WebError::ResponseText)?;
+    let text = JsFuture::from(text_promise)
+        .await
+        .map_err(|_| WebError::ResponseText)?;
+
+    let response_text = text.as_string().unwrap_or_default();
+    debug!("response text as string: {:?}", response_text);
+
+    Ok(RawResponse {
+        status,
+        content_type,
+        body: response_text,
+    })
+}
+
+/// The key identifying identical queries: the operation name, the canonical variables and the
+/// query document. Operations with the same name can come from different documents.
+fn deduplication_key<Variables: serde::Serialize>(
+    query_body: &QueryBody<Variables>,
+) -> Result<String, serde_json::Error> {
+    Ok(format!(
+        "{}:{}:{}",
+        query_body.operation_name,
+        crate::canonical::canonical_variables(&query_body.variables)?,
+        query_body.query
+    ))
+}
+
+/// Interpret a response body according to its status code and media type.
+fn parse_response<Data: for<'de> Deserialize<'de>>(
+    raw_response: RawResponse,
+) -> Result<crate::Response<Data>, ClientError> {
+    let RawResponse {
+        status,
+        content_type,
+        body,
+    } = raw_response;
+
+    if !crate::http::is_graphql_response(status, content_type.as_deref()) {
+        return Err(ClientError::Http {
+            status,
+            content_type,
+            body,
+        });
     }
+
+    crate::Response::from_json(&body)
 }
 
 #[cfg(test)]
@@ -136,4 +257,92 @@ mod tests {
         Client::new("https://example.com/graphql");
         Client::new("/graphql");
     }
+
+    #[test]
+    fn error_status_with_graphql_response_media_type_is_parsed() {
+        let response: crate::Response<serde_json::Value> = parse_response(RawResponse {
+            status: 400,
+            content_type: Some(crate::http::GRAPHQL_RESPONSE_MEDIA_TYPE.to_owned()),
+            body: r#"{"errors": [{"message": "Unknown field `nme`"}]}"#.to_owned(),
+        })
+        .unwrap();
+
+        assert_eq!(response.data, None);
+        assert_eq!(
+            response.errors.unwrap()[0].message,
+            "Unknown field `nme`".to_owned()
+        );
+    }
+
+    #[test]
+    fn non_graphql_body_keeps_status_and_body() {
+        let result: Result<crate::Response<serde_json::Value>, _> = parse_response(RawResponse {
+            status: 502,
+            content_type: Some("text/html".to_owned()),
+            body: "<h1>Bad Gateway</h1>".to_owned(),
+        });
+
+        match result {
+            Err(ClientError::Http {
+                status,
+                content_type,
+                body,
+            }) => {
+                assert_eq!(status, 502);
+                assert_eq!(content_type.as_deref(), Some("text/html"));
+                assert_eq!(body, "<h1>Bad Gateway</h1>");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    const HERO_AND_VILLAIN: &str = "query Hero($episode: Episode) { hero { name } } \
+         query Villain($episode: Episode) { villain { name } }";
+
+    #[test]
+    fn deduplication_key_includes_the_operation_name() {
+        let body = |operation_name| QueryBody {
+            variables: serde_json::json!({ "episode": "JEDI" }),
+            query: HERO_AND_VILLAIN,
+            operation_name,
+        };
+
+        assert_eq!(
+            deduplication_key(&body("Hero")).unwrap(),
+            format!(r#"Hero:{{"episode":"JEDI"}}:{}"#, HERO_AND_VILLAIN)
+        );
+        assert_ne!(
+            deduplication_key(&body("Hero")).unwrap(),
+            deduplication_key(&body("Villain")).unwrap()
+        );
+    }
+
+    #[test]
+    fn deduplication_key_includes_the_query() {
+        let body = |query| QueryBody {
+            variables: serde_json::json!({ "episode": "JEDI" }),
+            query,
+            operation_name: "Hero",
+        };
+
+        assert_ne!(
+            deduplication_key(&body("query Hero($episode: Episode) { hero { name } }")).unwrap(),
+            deduplication_key(&body("query Hero($episode: Episode) { hero { id } }")).unwrap()
+        );
+    }
+
+    #[test]
+    fn anonymous_operations_do_not_share_a_deduplication_key() {
+        // Anonymous operations are sent with an empty operation name.
+        let body = |query| QueryBody {
+            variables: serde_json::Value::Null,
+            query,
+            operation_name: "",
+        };
+
+        assert_ne!(
+            deduplication_key(&body("{ hero { name } }")).unwrap(),
+            deduplication_key(&body("{ villain { name } }")).unwrap()
+        );
+    }
 }
@@ -27,3 +27,28 @@ fn alias() {
         "inner value"
     );
 }
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/alias/query.graphql",
+    schema_path = "tests/alias/schema.graphql"
+)]
+pub struct MergedSelectionsQuery;
+
+#[test]
+fn repeated_selections_are_merged() {
+    let valid_response = json!({
+        "address": "127.0.1.2",
+        "nested": {
+            "inner": "inner value",
+        },
+    });
+
+    let response =
+        serde_json::from_value::<merged_selections_query::ResponseData>(valid_response).unwrap();
+
+    assert_eq!(response.address.unwrap(), "127.0.1.2");
+    // `inner` is selected both directly and through `NestFields`, so the fragment is merged into
+    // the `nested` struct.
+    assert_eq!(response.nested.unwrap().inner.unwrap(), "inner value");
+}
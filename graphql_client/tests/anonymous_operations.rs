@@ -0,0 +1,51 @@
+use graphql_client::GraphQLQuery;
+use serde_json::json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/anonymous_operations/anonymous.graphql",
+    schema_path = "tests/operation_selection/schema.graphql",
+    response_derives = "Debug,PartialEq"
+)]
+pub struct AnonymousEcho;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/anonymous_operations/shorthand.graphql",
+    schema_path = "tests/operation_selection/schema.graphql",
+    response_derives = "Debug,PartialEq"
+)]
+pub struct ShorthandHeight;
+
+#[test]
+fn anonymous_operations_are_named_after_the_struct() {
+    let response: anonymous_echo::ResponseData =
+        serde_json::from_value(json!({ "echo": "hi" })).unwrap();
+    assert_eq!(response.echo.as_deref(), Some("hi"));
+
+    let response: shorthand_height::ResponseData =
+        serde_json::from_value(json!({ "mountainHeight": 2784 })).unwrap();
+    assert_eq!(response.mountain_height, Some(2784));
+}
+
+#[test]
+fn anonymous_operations_are_sent_without_operation_name() {
+    let body = AnonymousEcho::build_query(anonymous_echo::Variables {
+        msg: "hi".to_owned(),
+    });
+
+    assert_eq!(anonymous_echo::OPERATION_NAME, "");
+    assert_eq!(
+        serde_json::to_value(&body).unwrap(),
+        json!({
+            "variables": { "msg": "hi" },
+            "query": anonymous_echo::QUERY,
+        })
+    );
+
+    let body = ShorthandHeight::build_query(shorthand_height::Variables);
+    assert!(serde_json::to_value(&body)
+        .unwrap()
+        .get("operationName")
+        .is_none());
+}
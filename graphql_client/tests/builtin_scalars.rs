@@ -0,0 +1,112 @@
+use graphql_client::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemId(String);
+
+impl From<&str> for ItemId {
+    fn from(id: &str) -> Self {
+        ItemId(id.to_owned())
+    }
+}
+
+impl From<i64> for ItemId {
+    fn from(id: i64) -> Self {
+        ItemId(id.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Count(i64);
+
+impl From<i64> for Count {
+    fn from(count: i64) -> Self {
+        Count(count)
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Weight(f64);
+
+impl From<f64> for Weight {
+    fn from(weight: f64) -> Self {
+        Weight(weight)
+    }
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/builtin_scalars/query.graphql",
+    schema_path = "tests/builtin_scalars/schema.graphql",
+    response_derives = "Debug",
+    scalars(Int = "i32", Float = "f32", ID = "crate::ItemId")
+)]
+pub struct ItemQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/builtin_scalars/newtypes_query.graphql",
+    schema_path = "tests/builtin_scalars/schema.graphql",
+    response_derives = "Debug",
+    scalars(Int = "crate::Count", Float = "crate::Weight", ID = "crate::ItemId")
+)]
+pub struct NewtypesQuery;
+
+#[test]
+fn builtin_scalars_in_variables_and_defaults() {
+    let variables = item_query::Variables {
+        id: item_query::Variables::default_id(),
+        filter: item_query::Variables::default_filter(),
+        weight: item_query::Variables::default_weight(),
+    };
+
+    let min_count: Option<i32> = variables.filter.as_ref().unwrap().minCount;
+    let weight: Option<f32> = variables.weight;
+
+    assert_eq!(variables.id, ItemId("item-1".to_owned()));
+    assert_eq!(min_count, Some(2));
+    assert_eq!(weight, Some(1.5));
+    assert_eq!(
+        serde_json::to_value(&variables).unwrap(),
+        json!({
+            "id": "item-1",
+            "filter": { "minCount": 2, "maxWeight": 3.0 },
+            "weight": 1.5,
+        })
+    );
+}
+
+#[test]
+fn numeric_defaults_of_newtype_scalars() {
+    let variables = newtypes_query::Variables {
+        id: newtypes_query::Variables::default_id(),
+        filter: newtypes_query::Variables::default_filter(),
+        weight: newtypes_query::Variables::default_weight(),
+    };
+
+    assert_eq!(variables.id, ItemId("5".to_owned()));
+    assert_eq!(variables.filter.as_ref().unwrap().minCount, Some(Count(2)));
+    assert_eq!(
+        variables.filter.as_ref().unwrap().maxWeight,
+        Some(Weight(3.0))
+    );
+    assert_eq!(variables.weight, Some(Weight(1.5)));
+}
+
+#[test]
+fn builtin_scalars_in_responses() {
+    let response = json!({
+        "item": { "id": "item-2", "count": 7, "weight": null },
+    });
+
+    let item = serde_json::from_value::<item_query::ResponseData>(response)
+        .unwrap()
+        .item
+        .unwrap();
+    let count: i32 = item.count;
+
+    assert_eq!(item.id, ItemId("item-2".to_owned()));
+    assert_eq!(count, 7);
+    assert_eq!(item.weight, None);
+}
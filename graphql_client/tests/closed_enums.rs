@@ -0,0 +1,74 @@
+use graphql_client::*;
+use serde_json::json;
+use std::collections::HashSet;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/closed_enums/query.graphql",
+    schema_path = "tests/closed_enums/schema.graphql",
+    response_derives = "Debug",
+    closed_enums("IssueState")
+)]
+pub struct IssuesQuery;
+
+use issues_query::IssueState;
+
+#[test]
+fn closed_enums_are_exhaustive() {
+    let response = json!({
+        "issues": [
+            { "title": "Crash on startup", "state": "OPEN" },
+            { "title": "Typo in the README", "state": "CLOSED" },
+            { "title": "Slow builds", "state": "OPEN" },
+        ],
+    });
+
+    let data = serde_json::from_value::<issues_query::ResponseData>(response).unwrap();
+    let states: HashSet<IssueState> = data.issues.iter().map(|issue| issue.state).collect();
+
+    assert_eq!(states.len(), 2);
+
+    let open = data
+        .issues
+        .iter()
+        .filter(|issue| match issue.state {
+            IssueState::OPEN => true,
+            IssueState::CLOSED => false,
+        })
+        .count();
+
+    assert_eq!(open, 2);
+    assert_eq!(
+        serde_json::to_value(issues_query::Variables {
+            state: Some(IssueState::CLOSED),
+        })
+        .unwrap(),
+        json!({ "state": "CLOSED" })
+    );
+}
+
+#[test]
+fn closed_enums_reject_unknown_values() {
+    let response = json!({
+        "issues": [{ "title": "Duplicate", "state": "WONTFIX" }],
+    });
+
+    let err = serde_json::from_value::<issues_query::ResponseData>(response).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "unknown variant `WONTFIX`, expected `OPEN` or `CLOSED`"
+    );
+}
+
+#[test]
+fn closed_enums_reject_unknown_strings() {
+    assert_eq!("CLOSED".parse(), Ok(IssueState::CLOSED));
+
+    let err = "WONTFIX".parse::<IssueState>().unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "`WONTFIX` is not a value of the `IssueState` enum"
+    );
+}
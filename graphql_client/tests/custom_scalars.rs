@@ -33,3 +33,31 @@ fn custom_scalars() {
 
     assert!(serde_json::from_value::<custom_scalar_query::ResponseData>(invalid_response).is_err());
 }
+
+pub mod mapped {
+    use graphql_client::*;
+    use serde_json::json;
+
+    // No `NetworkAddress` alias in scope: the scalar is mapped in the attribute.
+    #[derive(GraphQLQuery)]
+    #[graphql(
+        query_path = "tests/custom_scalars/mapped_query.graphql",
+        schema_path = "tests/custom_scalars/schema.graphql",
+        scalars(NetworkAddress = "std::net::Ipv4Addr")
+    )]
+    pub struct MappedScalarQuery;
+
+    #[test]
+    fn mapped_custom_scalars() {
+        let response = json!({
+            "address": "127.0.1.2",
+        });
+
+        let data = serde_json::from_value::<mapped_scalar_query::ResponseData>(response).unwrap();
+
+        assert_eq!(
+            data.address.unwrap(),
+            "127.0.1.2".parse::<std::net::Ipv4Addr>().unwrap()
+        );
+    }
+}
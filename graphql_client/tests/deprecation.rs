@@ -47,10 +47,8 @@ fn deprecation_deny() {
         current_user: Some(deny_deprecation::DenyDeprecationCurrentUser {
             id: Some("abcd".to_owned()),
             name: Some("Angela Merkel".to_owned()),
-            // Notice the deprecated fields are not included here.
-            // If they were generated, not using them would be a compile error.
-            // Thus this is checking that the depreacted fields are not
-            // generated under the `deny` scheme.
+            // Under `deny`, selecting the deprecated fields is a code generation
+            // error, so the query only selects the fields that are not deprecated.
         }),
     };
 }
@@ -67,3 +65,69 @@ fn deprecation_warn() {
         }),
     };
 }
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/deprecation/schema.graphql",
+    query_path = "tests/deprecation/inputs_query.graphql",
+    deprecated = "allow"
+)]
+pub struct AllowInputDeprecation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/deprecation/schema.graphql",
+    query_path = "tests/deprecation/inputs_query.graphql",
+    deprecated = "deny"
+)]
+pub struct DenyInputDeprecation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/deprecation/schema.graphql",
+    query_path = "tests/deprecation/inputs_query.graphql",
+    deprecated = "warn"
+)]
+pub struct WarnInputDeprecation;
+
+#[test]
+fn input_deprecation_allow() {
+    // Deprecated variables, input fields and enum values are not marked as such.
+    #![deny(deprecated)]
+    let _ = allow_input_deprecation::Variables {
+        filter: Some(allow_input_deprecation::UserFilter {
+            name: None,
+            legacyId: Some("abcd".to_owned()),
+        }),
+        legacy_role: Some(allow_input_deprecation::Role::EDITOR),
+    };
+}
+
+#[test]
+fn input_deprecation_deny() {
+    // Deprecated input fields and enum values are generated without annotation: only using them
+    // in the query is an error.
+    #![deny(deprecated)]
+    let _ = deny_input_deprecation::Variables {
+        filter: Some(deny_input_deprecation::UserFilter {
+            name: Some("Angela Merkel".to_owned()),
+            legacyId: Some("abcd".to_owned()),
+        }),
+    };
+
+    let role: deny_input_deprecation::Role = serde_json::from_str(r#""EDITOR""#).unwrap();
+
+    assert_eq!(role.as_str(), deny_input_deprecation::Role::EDITOR.as_str());
+}
+
+#[test]
+fn input_deprecation_warn() {
+    #![allow(deprecated)]
+    let _ = warn_input_deprecation::Variables {
+        filter: Some(warn_input_deprecation::UserFilter {
+            name: None,
+            legacyId: Some("abcd".to_owned()),
+        }),
+        legacy_role: Some(warn_input_deprecation::Role::EDITOR),
+    };
+}
@@ -0,0 +1,31 @@
+use graphql_client::*;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/closed_enums/query.graphql",
+    schema_path = "tests/closed_enums/schema.graphql",
+    response_derives = "Debug, PartialEq"
+)]
+pub struct IssuesQuery;
+
+use issues_query::IssueState;
+
+#[test]
+fn enums_list_their_values() {
+    assert_eq!(IssueState::ALL, &[IssueState::OPEN, IssueState::CLOSED]);
+}
+
+#[test]
+fn enums_convert_to_and_from_strings() {
+    let values: Vec<String> = IssueState::ALL.iter().map(ToString::to_string).collect();
+
+    assert_eq!(values, vec!["OPEN".to_owned(), "CLOSED".to_owned()]);
+    assert_eq!(IssueState::CLOSED.as_str(), "CLOSED");
+    assert_eq!("OPEN".parse(), Ok(IssueState::OPEN));
+
+    let other: IssueState = "WONTFIX".parse().unwrap();
+
+    assert_eq!(other, IssueState::Other("WONTFIX".to_owned()));
+    assert_eq!(other.as_str(), "WONTFIX");
+    assert_eq!(other.to_string(), "WONTFIX");
+}
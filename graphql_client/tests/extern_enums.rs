@@ -0,0 +1,50 @@
+use graphql_client::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// Shared by both operations, instead of an `Episode` enum generated in each module.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Episode {
+    Newhope,
+    Empire,
+    Jedi,
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/extern_enums/query.graphql",
+    schema_path = "tests/extern_enums/schema.graphql",
+    extern_enums("Episode")
+)]
+pub struct HeroAppearancesQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/extern_enums/query.graphql",
+    schema_path = "tests/extern_enums/schema.graphql",
+    extern_enums("Episode")
+)]
+pub struct HeroForEpisodeQuery;
+
+#[test]
+fn extern_enums_are_shared_between_operations() {
+    let response = json!({
+        "hero": { "appearsIn": ["NEWHOPE", "JEDI"] },
+    });
+
+    let appearances =
+        serde_json::from_value::<hero_appearances_query::ResponseData>(response).unwrap();
+    let appears_in: Vec<Episode> = appearances.hero.unwrap().appears_in;
+
+    assert_eq!(appears_in, vec![Episode::Newhope, Episode::Jedi]);
+
+    let variables = hero_for_episode_query::Variables {
+        episode: Some(appears_in[1]),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&variables).unwrap(),
+        json!({ "episode": "JEDI" })
+    );
+}
@@ -58,3 +58,33 @@ fn recursive_fragment() {
         })),
     };
 }
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/fragments/query.graphql",
+    schema_path = "tests/fragments/schema.graphql"
+)]
+pub struct MutuallyRecursiveFragmentQuery;
+
+#[test]
+fn mutually_recursive_fragments() {
+    let response = json!({
+        "recursive": {
+            "head": "ABCD",
+            "tail": {
+                "__typename": "RecursiveNode",
+                "head": "EFGH",
+                "tail": null,
+            },
+        },
+    });
+
+    let response =
+        serde_json::from_value::<mutually_recursive_fragment_query::ResponseData>(response)
+            .unwrap();
+
+    let tail = response.recursive.tail.unwrap();
+    assert_eq!(response.recursive.head.unwrap(), "ABCD");
+    assert_eq!(tail.mutually_recursive_a.head.as_deref(), Some("EFGH"));
+    assert!(tail.mutually_recursive_a.tail.is_none());
+}
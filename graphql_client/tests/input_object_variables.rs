@@ -11,14 +11,14 @@ pub struct InputObjectVariablesQuery;
 #[test]
 fn input_object_variables_query_variables_struct() {
     let _ = input_object_variables_query::Variables {
-        msg: Some(input_object_variables_query::Message {
+        msg: input_object_variables_query::Message {
             content: None,
             to: Some(input_object_variables_query::Recipient {
                 email: "sarah.connor@example.com".to_string(),
                 category: None,
                 name: Some("Sarah Connor".to_string()),
             }),
-        }),
+        },
     };
 }
 
@@ -26,12 +26,12 @@ fn operation_selection_works() {
     let echo_response_data: echo::ResponseData = serde_json::from_str(ECHO_RESPONSE).unwrap();
 
     let _echo_variables = echo::Variables {
-        msg: Some("hi".to_string()),
+        msg: "hi".to_string(),
     };
 
     let _height_variables = heights::Variables {
         building_id: "12".to_string(),
-        mountain_name: Some("canigou".to_string()),
+        mountain_name: "canigou".to_string(),
     };
 
     let expected_echo = echo::ResponseData {
@@ -50,12 +50,12 @@ fn operation_selection_works() {
 #[test]
 fn operation_name_is_correct() {
     let echo_variables = echo::Variables {
-        msg: Some("hi".to_string()),
+        msg: "hi".to_string(),
     };
 
     let height_variables = heights::Variables {
         building_id: "12".to_string(),
-        mountain_name: Some("canigou".to_string()),
+        mountain_name: "canigou".to_string(),
     };
 
     assert_eq!(Echo::build_query(echo_variables).operation_name, "Echo");
@@ -65,3 +65,11 @@ fn operation_name_is_correct() {
         "Heights"
     );
 }
+
+#[test]
+fn operation_type_is_set() {
+    assert_eq!(
+        <Echo as GraphQLQuery>::OPERATION_TYPE,
+        Some(graphql_client::OperationType::Query)
+    );
+}
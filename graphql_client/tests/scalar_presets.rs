@@ -0,0 +1,88 @@
+use graphql_client::*;
+use serde_json::json;
+
+// The `json` preset needs no cargo feature.
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/scalar_presets/query.graphql",
+    schema_path = "tests/scalar_presets/schema.graphql",
+    scalar_presets("json")
+)]
+pub struct JsonPresetQuery;
+
+#[test]
+fn json_scalar_preset() {
+    let response = json!({
+        "metadata": { "tags": ["a", "b"], "count": 2 },
+    });
+
+    let data = serde_json::from_value::<json_preset_query::ResponseData>(response).unwrap();
+
+    assert_eq!(data.metadata["tags"][1], "b");
+    assert_eq!(data.metadata["count"], 2);
+}
+
+pub mod without_presets {
+    use super::*;
+
+    // Without `scalar_presets`, the alias next to the struct is used, whatever the enabled
+    // features.
+    #[allow(clippy::upper_case_acronyms)]
+    type JSON = std::collections::BTreeMap<String, u32>;
+
+    #[derive(GraphQLQuery)]
+    #[graphql(
+        query_path = "tests/scalar_presets/query.graphql",
+        schema_path = "tests/scalar_presets/schema.graphql"
+    )]
+    pub struct JsonPresetQuery;
+
+    #[test]
+    fn aliases_are_used_without_presets() {
+        let response = json!({ "metadata": { "count": 2 } });
+
+        let data = serde_json::from_value::<json_preset_query::ResponseData>(response).unwrap();
+
+        assert_eq!(data.metadata.get("count"), Some(&2));
+    }
+}
+
+#[cfg(all(
+    feature = "scalars-chrono",
+    feature = "scalars-uuid",
+    feature = "scalars-url",
+    feature = "scalars-decimal"
+))]
+pub mod all_presets {
+    use super::*;
+    use chrono::{Datelike, Timelike};
+
+    #[derive(GraphQLQuery)]
+    #[graphql(
+        query_path = "tests/scalar_presets/query.graphql",
+        schema_path = "tests/scalar_presets/schema.graphql",
+        scalar_presets("chrono", "uuid", "url", "json", "decimal")
+    )]
+    pub struct AllPresetsQuery;
+
+    #[test]
+    fn all_scalar_presets() {
+        let response = json!({
+            "metadata": null,
+            "createdAt": "2020-05-04T12:30:00Z",
+            "birthday": "1990-01-31",
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "homepage": "https://example.com/users/1",
+            "balance": "12.50",
+        });
+
+        let data = serde_json::from_value::<all_presets_query::ResponseData>(response).unwrap();
+
+        assert!(data.metadata.is_null());
+        assert_eq!(data.created_at.hour(), 12);
+        assert_eq!(data.birthday.unwrap().month(), 1);
+        assert_eq!(data.id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(data.homepage.unwrap().path(), "/users/1");
+        assert_eq!(data.balance.to_string(), "12.50");
+    }
+}
@@ -0,0 +1,92 @@
+use graphql_client::*;
+use serde_json::json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/skip_include/query.graphql",
+    schema_path = "tests/skip_include/schema.graphql",
+    response_derives = "Debug"
+)]
+pub struct SkipIncludeQuery;
+
+#[test]
+fn skipped_selections_are_none() {
+    use skip_include_query::*;
+
+    let response = json!({
+        "hero": {},
+        "search": [{"__typename": "Droid"}, {"__typename": "Character"}],
+    });
+
+    let response = serde_json::from_value::<ResponseData>(response).unwrap();
+
+    assert!(response.hero.name.is_none());
+    assert!(response.hero.friends.is_none());
+    assert!(response.hero.character_height.is_none());
+
+    match &response.search[0] {
+        SkipIncludeQuerySearch::Droid(droid) => assert!(droid.primary_function.is_none()),
+        other => panic!("unexpected variant: {:?}", other),
+    }
+}
+
+#[test]
+fn included_selections_are_some() {
+    use skip_include_query::*;
+
+    let response = json!({
+        "hero": {
+            "name": "Leia",
+            "friends": [{"name": "Luke"}],
+            "height": 1.5,
+        },
+        "search": [{"__typename": "Droid", "primaryFunction": "Protocol"}],
+    });
+
+    let response = serde_json::from_value::<ResponseData>(response).unwrap();
+
+    assert_eq!(response.hero.name.as_deref(), Some("Leia"));
+    assert_eq!(response.hero.friends.unwrap()[0].name, "Luke");
+    assert_eq!(response.hero.character_height.unwrap().height, 1.5);
+
+    match &response.search[0] {
+        SkipIncludeQuerySearch::Droid(droid) => {
+            assert_eq!(droid.primary_function.as_deref(), Some("Protocol"))
+        }
+        other => panic!("unexpected variant: {:?}", other),
+    }
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/skip_include/query.graphql",
+    schema_path = "tests/skip_include/schema.graphql",
+    response_derives = "Debug"
+)]
+pub struct InlineFragmentsQuery;
+
+#[test]
+fn inline_fragments_on_the_parent_type_are_merged() {
+    let response = json!({
+        "hero": {
+            "name": "Leia",
+            "height": 1.5,
+            "friends": [{"name": "Luke"}],
+        },
+    });
+
+    let response =
+        serde_json::from_value::<inline_fragments_query::ResponseData>(response).unwrap();
+
+    assert_eq!(response.hero.name, "Leia");
+    assert_eq!(response.hero.height, Some(1.5));
+    assert_eq!(response.hero.friends.unwrap()[0].name, "Luke");
+
+    let response = serde_json::from_value::<inline_fragments_query::ResponseData>(
+        json!({ "hero": { "name": "Leia" } }),
+    )
+    .unwrap();
+
+    assert!(response.hero.height.is_none());
+    assert!(response.hero.friends.is_none());
+}
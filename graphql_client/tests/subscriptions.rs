@@ -46,4 +46,9 @@ fn subscriptions_work() {
         response_data.dog_birthdays.map(|birthdays| birthdays.len()),
         Some(4)
     );
+
+    assert_eq!(
+        SubscriptionQuery::OPERATION_TYPE,
+        Some(OperationType::Subscription)
+    );
 }
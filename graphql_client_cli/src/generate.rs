@@ -1,10 +1,12 @@
 use anyhow::*;
 use graphql_client_codegen::{
-    generate_module_token_stream, CodegenMode, GraphQLClientCodegenOptions,
+    generate_module_token_stream, scalar_presets::ScalarPreset, CodegenMode,
+    GraphQLClientCodegenOptions,
 };
 use std::fs::File;
 use std::io::Write as _;
 use std::path::PathBuf;
+use std::str::FromStr;
 use syn::Token;
 
 pub(crate) struct CliCodegenParams {
@@ -17,6 +19,43 @@ pub(crate) struct CliCodegenParams {
     pub no_formatting: bool,
     pub module_visibility: Option<String>,
     pub output_directory: Option<PathBuf>,
+    pub scalars: Vec<ScalarMapping>,
+    pub scalar_presets: Vec<ScalarPreset>,
+    pub extern_enums: Vec<String>,
+    pub closed_enums: Vec<String>,
+    pub all_enums_closed: bool,
+}
+
+/// A custom scalar mapped to a Rust type with `--scalar Name=path::to::Type`.
+#[derive(Debug, PartialEq)]
+pub struct ScalarMapping {
+    graphql_name: String,
+    rust_type: String,
+}
+
+impl FromStr for ScalarMapping {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let name_value: Vec<&str> = input.splitn(2, '=').map(str::trim).collect();
+
+        match name_value.as_slice() {
+            [graphql_name, rust_type] if !graphql_name.is_empty() => {
+                syn::parse_str::<syn::Type>(rust_type).map_err(|err| {
+                    format_err!("Invalid Rust type for the {} scalar: {}", graphql_name, err)
+                })?;
+
+                Ok(ScalarMapping {
+                    graphql_name: graphql_name.to_string(),
+                    rust_type: rust_type.to_string(),
+                })
+            }
+            _ => Err(format_err!(
+                "Invalid scalar mapping. Expected Name=path::to::Type. [{}]",
+                input
+            )),
+        }
+    }
 }
 
 pub(crate) fn generate_code(params: CliCodegenParams) -> Result<()> {
@@ -30,6 +69,11 @@ pub(crate) fn generate_code(params: CliCodegenParams) -> Result<()> {
         query_path,
         schema_path,
         selected_operation,
+        scalars,
+        scalar_presets,
+        extern_enums,
+        closed_enums,
+        all_enums_closed,
     } = params;
 
     let deprecation_strategy = deprecation_strategy.as_ref().and_then(|s| s.parse().ok());
@@ -59,7 +103,34 @@ pub(crate) fn generate_code(params: CliCodegenParams) -> Result<()> {
         options.set_deprecation_strategy(deprecation_strategy);
     }
 
-    let gen = generate_module_token_stream(query_path.clone(), &schema_path, options).unwrap();
+    for scalar in scalars {
+        options.set_custom_scalar(scalar.graphql_name, scalar.rust_type);
+    }
+
+    for preset in scalar_presets {
+        options.set_scalar_preset(preset);
+    }
+
+    for graphql_name in extern_enums {
+        options.set_extern_enum(graphql_name);
+    }
+
+    for graphql_name in closed_enums {
+        options.set_closed_enum(graphql_name);
+    }
+
+    if all_enums_closed {
+        options.set_all_enums_closed();
+    }
+
+    let gen =
+        generate_module_token_stream(query_path.clone(), &schema_path, options).map_err(|err| {
+            format_err!(
+                "Could not generate code for {}:\n{}",
+                query_path.display(),
+                err
+            )
+        })?;
 
     let generated_code = gen.to_string();
     let generated_code = if cfg!(feature = "rustfmt") && !no_formatting {
@@ -105,3 +176,27 @@ fn format(codes: &str) -> String {
     #[cfg(not(feature = "rustfmt"))]
     unreachable!("called format() without the rustfmt feature")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_scalar_mappings() {
+        assert_eq!(
+            ScalarMapping::from_str("DateTime = chrono::DateTime<chrono::Utc>").unwrap(),
+            ScalarMapping {
+                graphql_name: "DateTime".to_string(),
+                rust_type: "chrono::DateTime<chrono::Utc>".to_string(),
+            }
+        );
+
+        for input in ["DateTime", "=url::Url", "URI=url::"].iter() {
+            assert!(
+                ScalarMapping::from_str(input).is_err(),
+                "Expected error: [{}]",
+                input
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::*;
+use anyhow::{format_err, Result};
 use graphql_client::GraphQLQuery;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE};
 use std::path::PathBuf;
@@ -9,7 +9,7 @@ use std::str::FromStr;
     schema_path = "src/graphql/introspection_schema.graphql",
     query_path = "src/graphql/introspection_query.graphql",
     response_derives = "Serialize",
-    variable_derives = "Deserialize"
+    variables_derives = "Deserialize"
 )]
 #[allow(dead_code)]
 struct IntrospectionQuery;
@@ -50,12 +50,26 @@ pub fn introspect_schema(
 
     let res = req_builder.json(&request_body).send()?;
 
-    if res.status().is_success() {
-        // do nothing
-    } else if res.status().is_server_error() {
+    let status = res.status();
+    let content_type = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned);
+
+    if !graphql_client::http::is_graphql_response(status.as_u16(), content_type.as_deref()) {
+        return Err(format_err!(
+            "The server did not respond with a GraphQL response (status: {}, content type: {}).\n{}",
+            status,
+            content_type.as_deref().unwrap_or("none"),
+            res.text()?
+        ));
+    }
+
+    if status.is_server_error() {
         println!("server error!");
-    } else {
-        println!("Something else happened. Status: {:?}", res.status());
+    } else if !status.is_success() {
+        println!("Something else happened. Status: {:?}", status);
     }
 
     let json: serde_json::Value = res.json()?;
@@ -66,7 +80,10 @@ pub fn introspect_schema(
 fn construct_headers() -> HeaderMap {
     let mut headers = HeaderMap::new();
     headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    headers.insert(
+        ACCEPT,
+        HeaderValue::from_static(graphql_client::http::ACCEPT_HEADER),
+    );
     headers
 }
 
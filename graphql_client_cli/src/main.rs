@@ -6,6 +6,7 @@ extern crate rustfmt_nightly as rustfmt;
 
 mod generate;
 mod introspect_schema;
+use graphql_client_codegen::scalar_presets::ScalarPreset;
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -70,6 +71,26 @@ enum Cli {
         /// file, with the same name and the .rs extension.
         #[structopt(short = "o", long = "output-directory")]
         output_directory: Option<PathBuf>,
+        /// Map a custom scalar, or the built-in Int, Float and ID scalars, to a Rust type. Can be
+        /// repeated. --scalar 'DateTime=chrono::DateTime<chrono::Utc>' --scalar Int=i32
+        #[structopt(long = "scalar", number_of_values = 1)]
+        scalars: Vec<generate::ScalarMapping>,
+        /// Map the common custom scalars of a preset to the types of its crate, re-exported by
+        /// graphql_client with the matching scalars-* feature. Can be repeated. One of chrono,
+        /// uuid, url, json or decimal. --scalar-preset chrono
+        #[structopt(long = "scalar-preset", number_of_values = 1)]
+        scalar_presets: Vec<ScalarPreset>,
+        /// Use an enum from the parent module instead of generating one. Can be repeated.
+        /// --extern-enum Episode
+        #[structopt(long = "extern-enum", number_of_values = 1)]
+        extern_enums: Vec<String>,
+        /// Generate an enum without the Other(String) variant. Can be repeated.
+        /// --closed-enum Episode
+        #[structopt(long = "closed-enum", number_of_values = 1)]
+        closed_enums: Vec<String>,
+        /// Generate all the enums without the Other(String) variant.
+        #[structopt(long = "closed-enums")]
+        all_enums_closed: bool,
     },
 }
 
@@ -101,6 +122,11 @@ fn main() -> anyhow::Result<()> {
             query_path,
             schema_path,
             selected_operation,
+            scalars,
+            scalar_presets,
+            extern_enums,
+            closed_enums,
+            all_enums_closed,
         } => generate::generate_code(generate::CliCodegenParams {
             variables_derives,
             response_derives,
@@ -111,6 +137,11 @@ fn main() -> anyhow::Result<()> {
             query_path,
             schema_path,
             selected_operation,
+            scalars,
+            scalar_presets,
+            extern_enums,
+            closed_enums,
+            all_enums_closed,
         }),
     }
 }
@@ -149,3 +180,48 @@ fn colored_level<'a>(style: &'a mut Style, level: Level) -> StyledValue<'a, &'st
         Level::Error => style.set_color(Color::Red).value("ERROR"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeatable_options_take_one_value() {
+        let cli = Cli::from_iter_safe(&[
+            "graphql-client",
+            "generate",
+            "--schema-path",
+            "schema.graphql",
+            "--scalar",
+            "Int=i32",
+            "--extern-enum",
+            "Episode",
+            "--closed-enum",
+            "Role",
+            "--scalar",
+            "ID=String",
+            "--scalar-preset",
+            "chrono",
+            "query.graphql",
+        ])
+        .unwrap();
+
+        match cli {
+            Cli::Generate {
+                query_path,
+                scalars,
+                scalar_presets,
+                extern_enums,
+                closed_enums,
+                ..
+            } => {
+                assert_eq!(query_path, PathBuf::from("query.graphql"));
+                assert_eq!(scalars.len(), 2);
+                assert_eq!(scalar_presets, vec![ScalarPreset::Chrono]);
+                assert_eq!(extern_enums, vec!["Episode"]);
+                assert_eq!(closed_enums, vec!["Role"]);
+            }
+            Cli::IntrospectSchema { .. } => panic!("expected the generate subcommand"),
+        }
+    }
+}
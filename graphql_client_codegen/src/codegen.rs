@@ -5,7 +5,7 @@ mod shared;
 
 use crate::{
     query::*,
-    schema::{InputId, TypeId},
+    schema::{collect_deprecated_literals, InputId, StoredField, StoredFieldArgument, TypeId},
     type_qualifiers::GraphqlTypeQualifier,
     GeneralError, GraphQLClientCodegenOptions,
 };
@@ -22,11 +22,14 @@ pub(crate) fn response_for_query(
     query: BoundQuery<'_>,
 ) -> Result<TokenStream, GeneralError> {
     let all_used_types = all_used_types(operation_id, &query);
+    let deprecated_arguments = deprecated_arguments(operation_id, &query);
+
     let response_derives = render_derives(options.all_response_derives());
     let variable_derives = render_derives(options.all_variable_derives());
 
-    let scalar_definitions = generate_scalar_definitions(&all_used_types, options, query);
-    let enum_definitions = enums::generate_enum_definitions(&all_used_types, options, query);
+    let builtin_scalar_definitions = generate_builtin_scalar_definitions(options)?;
+    let scalar_definitions = generate_scalar_definitions(&all_used_types, options, query)?;
+    let enum_definitions = enums::generate_enum_definitions(&all_used_types, options, query)?;
     let fragment_definitions =
         generate_fragment_definitions(&all_used_types, &response_derives, options, &query);
     let input_object_definitions = inputs::generate_input_object_definitions(
@@ -36,8 +39,13 @@ pub(crate) fn response_for_query(
         &query,
     );
 
-    let variables_struct =
-        generate_variables_struct(operation_id, &variable_derives, options, &query);
+    let variables_struct = generate_variables_struct(
+        operation_id,
+        &variable_derives,
+        &deprecated_arguments,
+        options,
+        &query,
+    )?;
 
     let definitions =
         render_response_data_fields(operation_id, options, &query).render(&response_derives);
@@ -46,14 +54,7 @@ pub(crate) fn response_for_query(
         use serde::{Serialize, Deserialize};
         use super::*;
 
-        #[allow(dead_code)]
-        type Boolean = bool;
-        #[allow(dead_code)]
-        type Float = f64;
-        #[allow(dead_code)]
-        type Int = i64;
-        #[allow(dead_code)]
-        type ID = String;
+        #(#builtin_scalar_definitions)*
 
         #(#scalar_definitions)*
 
@@ -71,47 +72,104 @@ pub(crate) fn response_for_query(
     Ok(q)
 }
 
+/// The deprecated arguments the operation passes values to, with the values.
+fn deprecated_arguments<'a>(
+    operation_id: OperationId,
+    query: &BoundQuery<'a>,
+) -> Vec<(
+    &'a StoredField,
+    &'a StoredFieldArgument,
+    &'a graphql_parser::query::Value,
+)> {
+    walk_operation_fields(operation_id, query.query)
+        .into_iter()
+        .flat_map(|(_id, field)| {
+            let schema_field = query.schema.get_field(field.field_id);
+
+            field.arguments.iter().filter_map(move |(name, value)| {
+                schema_field
+                    .get_argument(name)
+                    .filter(|argument| argument.deprecation().is_some())
+                    .map(|argument| (schema_field, argument, value))
+            })
+        })
+        .collect()
+}
+
 fn generate_variables_struct(
     operation_id: OperationId,
     variable_derives: &impl quote::ToTokens,
+    deprecated_arguments: &[(
+        &StoredField,
+        &StoredFieldArgument,
+        &graphql_parser::query::Value,
+    )],
     options: &GraphQLClientCodegenOptions,
     query: &BoundQuery<'_>,
-) -> TokenStream {
+) -> Result<TokenStream, GeneralError> {
     if operation_has_no_variables(operation_id, query.query) {
-        return quote!(
+        return Ok(quote!(
             #variable_derives
             pub struct Variables;
-        );
+        ));
     }
 
-    let variable_fields = walk_operation_variables(operation_id, query.query)
-        .map(|(_id, variable)| generate_variable_struct_field(variable, options, query));
-    let variable_defaults =
+    let variable_fields =
         walk_operation_variables(operation_id, query.query).map(|(_id, variable)| {
-            let method_name = format!("default_{}", variable.name);
-            let method_name = Ident::new(&method_name, Span::call_site());
-            let method_return_type = render_variable_field_type(variable, options, query);
-
-            variable.default.as_ref().map(|default| {
-                let value = graphql_parser_value_to_literal(
-                    default,
-                    variable.r#type.id,
-                    variable
-                        .r#type
-                        .qualifiers
-                        .get(0)
-                        .map(|qual| !qual.is_required())
-                        .unwrap_or(true),
-                    query,
-                );
+            // Variables passed to deprecated arguments are deprecated.
+            let deprecation = deprecated_arguments
+                .iter()
+                .find(|(_, _, value)| match value {
+                    graphql_parser::query::Value::Variable(name) => *name == variable.name,
+                    _ => false,
+                })
+                .and_then(|(_, argument, _)| argument.deprecation());
 
-                quote!(
-                    pub fn #method_name() -> #method_return_type {
-                        #value
-                    }
-                )
-            })
+            generate_variable_struct_field(variable, deprecation, options, query)
         });
+    let mut variable_defaults = Vec::new();
+    let mut deprecated_literals = Vec::new();
+
+    for (_id, variable) in walk_operation_variables(operation_id, query.query) {
+        let default = match &variable.default {
+            Some(default) => default,
+            None => continue,
+        };
+        let method_name = format!("default_{}", variable.name);
+        let method_name = Ident::new(&method_name, Span::call_site());
+        let method_return_type = render_variable_field_type(variable, options, query);
+        let value = graphql_parser_value_to_literal(
+            default,
+            variable.r#type.id,
+            variable
+                .r#type
+                .qualifiers
+                .get(0)
+                .map(|qual| !qual.is_required())
+                .unwrap_or(true),
+            options,
+            query,
+        )?;
+
+        collect_deprecated_literals(
+            default,
+            variable.r#type.id,
+            query.schema,
+            &mut deprecated_literals,
+        );
+        variable_defaults.push(quote!(
+            pub fn #method_name() -> #method_return_type {
+                #value
+            }
+        ));
+    }
+
+    // Only default values using deprecated enum values or input fields need the allow.
+    let allow_deprecated = if deprecated_literals.is_empty() {
+        None
+    } else {
+        Some(quote!(#[allow(deprecated)]))
+    };
 
     let variables_struct = quote!(
         #variable_derives
@@ -119,16 +177,18 @@ fn generate_variables_struct(
             #(#variable_fields,)*
         }
 
+        #allow_deprecated
         impl Variables {
             #(#variable_defaults)*
         }
     );
 
-    variables_struct
+    Ok(variables_struct)
 }
 
 fn generate_variable_struct_field(
     variable: &ResolvedVariable,
+    deprecation: Option<Option<&str>>,
     options: &GraphQLClientCodegenOptions,
     query: &BoundQuery<'_>,
 ) -> TokenStream {
@@ -139,28 +199,104 @@ fn generate_variable_struct_field(
     );
     let annotation = shared::field_rename_annotation(&variable.name, &snake_case_name);
     let r#type = render_variable_field_type(variable, options, query);
+    let deprecation_annotation =
+        shared::deprecation_annotation(deprecation, &options.deprecation_strategy());
 
-    quote::quote!(#annotation pub #ident : #r#type)
+    quote::quote!(#annotation #deprecation_annotation pub #ident : #r#type)
 }
 
-fn generate_scalar_definitions<'a, 'schema: 'a>(
-    all_used_types: &'a crate::query::UsedTypes,
-    options: &'a GraphQLClientCodegenOptions,
-    query: BoundQuery<'schema>,
-) -> impl Iterator<Item = TokenStream> + 'a {
-    all_used_types
-        .scalars(query.schema)
-        .map(move |(_id, scalar)| {
-            let ident = syn::Ident::new(
-                options.normalization().scalar_name(&scalar.name).as_ref(),
-                proc_macro2::Span::call_site(),
-            );
-
-            quote!(type #ident = super::#ident;)
-        })
+/// The aliases for the built-in scalars. `Int`, `Float` and `ID` can be mapped to other Rust types,
+/// `Boolean` and `String` cannot.
+fn generate_builtin_scalar_definitions(
+    options: &GraphQLClientCodegenOptions,
+) -> Result<Vec<TokenStream>, GeneralError> {
+    for name in &["Boolean", "String"] {
+        if options.custom_scalar(name).is_some() {
+            return Err(GeneralError(format!(
+                "The built-in `{}` scalar cannot be mapped to another Rust type. Only `Int`, `Float` and `ID` can.",
+                name
+            )));
+        }
+    }
+
+    let mut definitions = vec![quote! {
+        #[allow(dead_code)]
+        type Boolean = bool;
+    }];
+
+    for (name, default_type) in &[("Float", "f64"), ("Int", "i64"), ("ID", "String")] {
+        let ident = Ident::new(name, Span::call_site());
+        let rust_type = options.custom_scalar(name).unwrap_or(default_type);
+        let rust_type: syn::Type = syn::parse_str(rust_type).map_err(|err| {
+            GeneralError(format!(
+                "Invalid Rust type `{}` for the `{}` scalar: {}",
+                rust_type, name, err
+            ))
+        })?;
+
+        definitions.push(quote! {
+            #[allow(dead_code)]
+            type #ident = #rust_type;
+        });
+    }
+
+    Ok(definitions)
 }
 
-fn render_derives<'a>(derives: impl Iterator<Item = &'a str>) -> impl quote::ToTokens {
+fn generate_scalar_definitions(
+    all_used_types: &crate::query::UsedTypes,
+    options: &GraphQLClientCodegenOptions,
+    query: BoundQuery<'_>,
+) -> Result<Vec<TokenStream>, GeneralError> {
+    let mut definitions = Vec::new();
+    let mut unmapped_scalars = Vec::new();
+
+    for (_id, scalar) in all_used_types.scalars(query.schema) {
+        let ident = syn::Ident::new(
+            options.normalization().scalar_name(&scalar.name).as_ref(),
+            proc_macro2::Span::call_site(),
+        );
+
+        let doc = shared::doc_comment(scalar.description.as_deref());
+        let rust_type = options
+            .custom_scalar(&scalar.name)
+            .or_else(|| options.scalar_preset_type(&scalar.name));
+
+        match rust_type {
+            Some(rust_type) => {
+                let rust_type: syn::Type = syn::parse_str(rust_type).map_err(|err| {
+                    GeneralError(format!(
+                        "Invalid Rust type `{}` for the `{}` scalar: {}",
+                        rust_type, scalar.name, err
+                    ))
+                })?;
+
+                definitions.push(quote!(#doc type #ident = #rust_type;));
+            }
+            None if options.has_custom_scalars() => unmapped_scalars.push(scalar.name.as_str()),
+            None => definitions.push(quote!(#doc type #ident = super::#ident;)),
+        }
+    }
+
+    if !unmapped_scalars.is_empty() {
+        unmapped_scalars.sort_unstable();
+
+        return Err(GeneralError(format!(
+            "The query uses custom scalars without a Rust type: {}. Map them to Rust types, e.g. with `scalars({} = \"path::to::Type\")` in the graphql attribute or `--scalar {}=path::to::Type` in the CLI.",
+            unmapped_scalars
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", "),
+            unmapped_scalars[0],
+            unmapped_scalars[0],
+        )));
+    }
+
+    Ok(definitions)
+}
+
+fn render_derives<'a>(derives: impl Iterator<Item = &'a str>) -> TokenStream {
     let idents = derives.map(|s| Ident::new(s, Span::call_site()));
 
     quote!(#[derive(#(#idents),*)])
@@ -233,8 +369,9 @@ fn graphql_parser_value_to_literal(
     value: &graphql_parser::query::Value,
     ty: TypeId,
     is_optional: bool,
+    options: &GraphQLClientCodegenOptions,
     query: &BoundQuery<'_>,
-) -> TokenStream {
+) -> Result<TokenStream, GeneralError> {
     use graphql_parser::query::Value;
 
     let inner = match value {
@@ -245,72 +382,173 @@ fn graphql_parser_value_to_literal(
                 quote!(false)
             }
         }
-        Value::String(s) => quote!(#s.to_string()),
+        // `Into`, and unsuffixed literals for primitive numbers, because `ID`, `Int` and `Float`
+        // can be mapped to other Rust types.
+        Value::String(s) => quote!(#s.into()),
         Value::Variable(_) => panic!("variable in variable"),
         Value::Null => panic!("null as default value"),
-        Value::Float(f) => quote!(#f),
+        Value::Float(f) => {
+            let rust_type = scalar_rust_type(ty.name(query.schema), options);
+            float_literal(*f, rust_type)
+        }
         Value::Int(i) => {
-            let i = i.as_i64();
-            quote!(#i)
+            let scalar_name = ty.name(query.schema);
+            let rust_type = scalar_rust_type(scalar_name, options);
+            let i = i.as_i64().ok_or_else(|| {
+                GeneralError(format!(
+                    "A default value for the `{}` scalar does not fit in 64 bits.",
+                    scalar_name
+                ))
+            })?;
+            let out_of_range = |constraint: String| {
+                GeneralError(format!(
+                    "The default value `{}` is out of range for the `{}` scalar: {}.",
+                    i, scalar_name, constraint
+                ))
+            };
+
+            if scalar_name == "Float" || rust_type.map(is_float_type).unwrap_or(false) {
+                float_literal(i as f64, rust_type)
+            } else {
+                // `Int` is a 32-bit integer in GraphQL, whatever its Rust type.
+                if scalar_name == "Int" && (i < i32::MIN.into() || i > i32::MAX.into()) {
+                    return Err(out_of_range("`Int` is a signed 32-bit integer".to_owned()));
+                }
+
+                if let Some((min, max)) = rust_type.and_then(integer_range) {
+                    if i128::from(i) < min || i128::from(i) > max {
+                        return Err(out_of_range(format!(
+                            "it does not fit in its Rust type, `{}`",
+                            rust_type.unwrap_or_default()
+                        )));
+                    }
+                }
+
+                match rust_type {
+                    Some(rust_type) if integer_range(rust_type).is_none() => {
+                        let i = proc_macro2::Literal::i64_suffixed(i);
+                        quote!(#i.into())
+                    }
+                    _ => {
+                        let i = proc_macro2::Literal::i64_unsuffixed(i);
+                        quote!(#i)
+                    }
+                }
+            }
         }
         Value::Enum(en) => quote!(#en),
         Value::List(inner) => {
             let elements = inner
                 .iter()
-                .map(|val| graphql_parser_value_to_literal(val, ty, false, query));
+                .map(|val| graphql_parser_value_to_literal(val, ty, false, options, query))
+                .collect::<Result<Vec<_>, _>>()?;
             quote! {
                 vec![
                     #(#elements,)*
                 ]
             }
         }
-        Value::Object(obj) => ty
-            .as_input_id()
-            .map(|input_id| render_object_literal(obj, input_id, query))
-            .unwrap_or_else(|| {
-                quote!(compile_error!(
-                    "Object literal on a non-input-object field."
-                ))
-            }),
+        Value::Object(obj) => match ty.as_input_id() {
+            Some(input_id) => render_object_literal(obj, input_id, options, query)?,
+            None => {
+                return Err(GeneralError(format!(
+                    "An object default value is given for `{}`, which is not an input object.",
+                    ty.name(query.schema)
+                )))
+            }
+        },
     };
 
     if is_optional {
-        quote!(Some(#inner))
+        Ok(quote!(Some(#inner)))
     } else {
-        inner
+        Ok(inner)
+    }
+}
+
+/// The Rust type of a scalar, if it is known, to render the numbers in default values.
+fn scalar_rust_type<'a>(
+    graphql_name: &str,
+    options: &'a GraphQLClientCodegenOptions,
+) -> Option<&'a str> {
+    options
+        .custom_scalar(graphql_name)
+        .or_else(|| options.scalar_preset_type(graphql_name))
+        .or(match graphql_name {
+            "Int" => Some("i64"),
+            "Float" => Some("f64"),
+            _ => None,
+        })
+        .map(str::trim)
+}
+
+/// A float default value: unsuffixed for primitive types, converted with `Into` from `f64` for
+/// the other mapped types.
+fn float_literal(f: f64, rust_type: Option<&str>) -> TokenStream {
+    match rust_type {
+        Some(rust_type) if !is_float_type(rust_type) => {
+            let f = proc_macro2::Literal::f64_suffixed(f);
+            quote!(#f.into())
+        }
+        _ => {
+            let f = proc_macro2::Literal::f64_unsuffixed(f);
+            quote!(#f)
+        }
     }
 }
 
+fn is_float_type(rust_type: &str) -> bool {
+    rust_type == "f32" || rust_type == "f64"
+}
+
+/// The values of a primitive integer type.
+fn integer_range(rust_type: &str) -> Option<(i128, i128)> {
+    let range = match rust_type {
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "i64" | "isize" => (i64::MIN.into(), i64::MAX.into()),
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" | "usize" => (0, u64::MAX.into()),
+        _ => return None,
+    };
+
+    Some(range)
+}
+
 /// For default value constructors.
 fn render_object_literal(
     object_map: &BTreeMap<String, graphql_parser::query::Value>,
     input_id: InputId,
+    options: &GraphQLClientCodegenOptions,
     query: &BoundQuery<'_>,
-) -> TokenStream {
+) -> Result<TokenStream, GeneralError> {
     let input = query.schema.get_input(input_id);
     let constructor = Ident::new(&input.name, Span::call_site());
-    let fields: Vec<TokenStream> = input
-        .fields
-        .iter()
-        .map(|(name, r#type)| {
-            let field_name = Ident::new(&name, Span::call_site());
-            let provided_value = object_map.get(name);
-            match provided_value {
-                Some(default_value) => {
-                    let value = graphql_parser_value_to_literal(
-                        default_value,
-                        r#type.id,
-                        r#type.is_optional(),
-                        query,
-                    );
-                    quote!(#field_name: #value)
-                }
-                None => quote!(#field_name: None),
+    let mut fields: Vec<TokenStream> = Vec::with_capacity(input.fields.len());
+
+    for (name, r#type) in &input.fields {
+        let field_name = Ident::new(&name, Span::call_site());
+        let provided_value = object_map.get(name);
+
+        match provided_value {
+            Some(default_value) => {
+                let value = graphql_parser_value_to_literal(
+                    default_value,
+                    r#type.id,
+                    r#type.is_optional(),
+                    options,
+                    query,
+                )?;
+                fields.push(quote!(#field_name: #value))
             }
-        })
-        .collect();
+            None => fields.push(quote!(#field_name: None)),
+        }
+    }
 
-    quote!(#constructor {
+    Ok(quote!(#constructor {
         #(#fields,)*
-    })
+    }))
 }
@@ -1,5 +1,12 @@
 use crate::{
-    codegen::render_derives, codegen_options::GraphQLClientCodegenOptions, query::BoundQuery,
+    codegen::{
+        render_derives,
+        shared::{deprecation_annotation, doc_comment},
+    },
+    codegen_options::GraphQLClientCodegenOptions,
+    query::BoundQuery,
+    schema::TypeId,
+    GeneralError,
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
@@ -15,20 +22,49 @@ pub(super) fn generate_enum_definitions<'a, 'schema: 'a>(
     all_used_types: &'a crate::query::UsedTypes,
     options: &'a GraphQLClientCodegenOptions,
     query: BoundQuery<'schema>,
-) -> impl Iterator<Item = TokenStream> + 'a {
-    let derives = render_derives(
+) -> Result<Vec<TokenStream>, GeneralError> {
+    check_enum_names("extern_enums", options.extern_enums(), &query)?;
+    check_enum_names("closed_enums", options.closed_enums(), &query)?;
+
+    let response_derives = || {
         options
             .all_response_derives()
-            .filter(|d| !&["Serialize", "Deserialize", "Default"].contains(d)),
+            .filter(|d| !&["Serialize", "Deserialize", "Default"].contains(d))
+    };
+    let derives = render_derives(response_derives());
+    // Closed enums only have unit variants, so these can always be derived.
+    let closed_enum_base_derives = ["Clone", "Copy", "PartialEq", "Eq", "Hash"];
+    let closed_enum_derives = render_derives(
+        closed_enum_base_derives
+            .iter()
+            .copied()
+            .chain(response_derives().filter(|d| !closed_enum_base_derives.contains(d))),
     );
     let normalization = options.normalization();
+    let deprecation_strategy = options.deprecation_strategy();
+
+    let definitions = all_used_types.enums(query.schema).map(move |(_id, r#enum)| {
+        if options.is_extern_enum(&r#enum.name) {
+            let name = Ident::new(&normalization.enum_name(r#enum.name.as_str()), Span::call_site());
 
-    all_used_types.enums(query.schema).map(move |(_id, r#enum)| {
-        let variant_names: Vec<TokenStream> = r#enum
+            return quote!(type #name = super::#name;);
+        }
+
+        let variants: Vec<(&str, TokenStream)> = r#enum
             .variants
             .iter()
             .map(|v| {
-                let safe_name = super::shared::keyword_replace(v.as_str());
+                let deprecation =
+                    deprecation_annotation(r#enum.variant_deprecation(v), &deprecation_strategy);
+                let doc = doc_comment(r#enum.variant_description(v));
+
+                (v.as_str(), quote!(#doc #deprecation))
+            })
+            .collect();
+        let variant_names: Vec<TokenStream> = variants
+            .iter()
+            .map(|(v, _)| {
+                let safe_name = super::shared::keyword_replace(*v);
                 let name = normalization.enum_variant(safe_name.as_ref());
                 let name = Ident::new(&name, Span::call_site());
 
@@ -36,13 +72,13 @@ pub(super) fn generate_enum_definitions<'a, 'schema: 'a>(
             })
             .collect();
         let variant_names = &variant_names;
+        let variant_annotations = variants.iter().map(|(_, annotation)| annotation);
         let name_ident = normalization.enum_name(r#enum.name.as_str());
         let name_ident = Ident::new(&name_ident, Span::call_site());
-        let constructors: Vec<_> = r#enum
-            .variants
+        let constructors: Vec<_> = variants
             .iter()
-            .map(|v| {
-                let safe_name = super::shared::keyword_replace(v);
+            .map(|(v, _)| {
+                let safe_name = super::shared::keyword_replace(*v);
                 let name = normalization.enum_variant(safe_name.as_ref());
                 let v = Ident::new(&name, Span::call_site());
 
@@ -50,36 +86,125 @@ pub(super) fn generate_enum_definitions<'a, 'schema: 'a>(
             })
             .collect();
         let constructors = &constructors;
-        let variant_str: Vec<&str> = r#enum.variants.iter().map(|s| s.as_str()).collect();
+        let variant_str: Vec<&str> = variants.iter().map(|(v, _)| *v).collect();
         let variant_str = &variant_str;
 
         let name = name_ident;
+        let graphql_name = r#enum.name.as_str();
+        let doc = doc_comment(r#enum.description.as_deref());
+
+        // An enum value named `ALL` would shadow the constant.
+        let all_ident = if variant_names.iter().any(|v| v.to_string() == "ALL") {
+            Ident::new("ALL_", Span::call_site())
+        } else {
+            Ident::new("ALL", Span::call_site())
+        };
+
+        let (derives, other_variant, as_str_other, deserialize_other, from_str_err, from_str_other) =
+            if options.is_closed_enum(&r#enum.name) {
+                (
+                    &closed_enum_derives,
+                    quote!(),
+                    quote!(),
+                    quote!(_ => Err(::serde::de::Error::unknown_variant(&s, &[#(#variant_str),*]))),
+                    quote!(::graphql_client::UnknownEnumValue),
+                    quote!(_ => Err(::graphql_client::UnknownEnumValue {
+                        enum_name: #graphql_name,
+                        value: s.to_owned(),
+                    })),
+                )
+            } else {
+                (
+                    &derives,
+                    quote!(Other(String),),
+                    quote!(#name::Other(ref s) => s.as_str(),),
+                    quote!(_ => Ok(#name::Other(s))),
+                    quote!(::std::convert::Infallible),
+                    quote!(_ => Ok(#name::Other(s.to_owned()))),
+                )
+            };
 
         quote! {
+            #doc
             #derives
             pub enum #name {
-                #(#variant_names,)*
-                Other(String),
+                #(#variant_annotations #variant_names,)*
+                #other_variant
             }
 
+            #[allow(deprecated)]
+            impl #name {
+                /// All the values of the enum in the schema, in the order of the schema.
+                pub const #all_ident: &'static [#name] = &[#(#constructors,)*];
+
+                /// The value of the enum in the schema.
+                pub fn as_str(&self) -> &str {
+                    match *self {
+                        #(#constructors => #variant_str,)*
+                        #as_str_other
+                    }
+                }
+            }
+
+            #[allow(deprecated)]
+            impl ::std::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            #[allow(deprecated)]
+            impl ::std::str::FromStr for #name {
+                type Err = #from_str_err;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    match s {
+                        #(#variant_str => Ok(#constructors),)*
+                        #from_str_other
+                    }
+                }
+            }
+
+            #[allow(deprecated)]
             impl ::serde::Serialize for #name {
                 fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
-                    ser.serialize_str(match *self {
-                        #(#constructors => #variant_str,)*
-                        #name::Other(ref s) => &s,
-                    })
+                    ser.serialize_str(self.as_str())
                 }
             }
 
+            #[allow(deprecated)]
             impl<'de> ::serde::Deserialize<'de> for #name {
                 fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
                     let s = <String>::deserialize(deserializer)?;
 
                     match s.as_str() {
                         #(#variant_str => Ok(#constructors),)*
-                        _ => Ok(#name::Other(s)),
+                        #deserialize_other
                     }
                 }
             }
-        }})
+        }});
+
+    Ok(definitions.collect())
+}
+
+/// The enums listed in an option must be enums in the schema.
+fn check_enum_names<'a>(
+    option: &str,
+    names: impl Iterator<Item = &'a str>,
+    query: &BoundQuery<'_>,
+) -> Result<(), GeneralError> {
+    for name in names {
+        match query.schema.find_type(name) {
+            Some(TypeId::Enum(_)) => (),
+            _ => {
+                return Err(GeneralError(format!(
+                    "`{}` is listed in {}, but it is not an enum in the schema.",
+                    name, option
+                )))
+            }
+        }
+    }
+
+    Ok(())
 }
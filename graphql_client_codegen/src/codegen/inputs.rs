@@ -1,4 +1,4 @@
-use super::shared::keyword_replace;
+use super::shared::{deprecation_annotation, doc_comment, keyword_replace};
 use crate::{
     codegen_options::GraphQLClientCodegenOptions,
     query::{BoundQuery, UsedTypes},
@@ -21,6 +21,11 @@ pub(super) fn generate_input_object_definitions(
             let struct_name = Ident::new(safe_name.as_ref(), Span::call_site());
 
             let fields = input.fields.iter().map(|(field_name, field_type)| {
+                let deprecation_annotation = deprecation_annotation(
+                    input.field_deprecation(field_name),
+                    &options.deprecation_strategy(),
+                );
+                let doc = doc_comment(input.field_description(field_name));
                 let safe_field_name = keyword_replace(field_name);
                 let name_ident = Ident::new(safe_field_name.as_ref(), Span::call_site());
                 let normalized_field_type_name = options
@@ -38,10 +43,13 @@ pub(super) fn generate_input_object_definitions(
                 } else {
                     field_type_tokens
                 };
-                quote!(pub #name_ident: #field_type)
+                quote!(#doc #deprecation_annotation pub #name_ident: #field_type)
             });
 
+            let doc = doc_comment(input.description.as_deref());
+
             quote! {
+                #doc
                 #variable_derives
                 pub struct #struct_name {
                     #(#fields,)*
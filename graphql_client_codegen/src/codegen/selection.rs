@@ -3,12 +3,12 @@
 use crate::{
     codegen::{
         decorate_type,
-        shared::{field_rename_annotation, keyword_replace},
+        shared::{deprecation_annotation, doc_comment, field_rename_annotation, keyword_replace},
     },
-    deprecation::DeprecationStrategy,
     query::{
-        fragment_is_recursive, full_path_prefix, BoundQuery, InlineFragment, OperationId,
-        ResolvedFragment, ResolvedFragmentId, SelectedField, Selection, SelectionId,
+        fragment_field_by_response_key, fragment_is_recursive, full_path_prefix,
+        merge_inline_fragments, BoundQuery, InlineFragment, OperationId, ResolvedFragment,
+        ResolvedFragmentId, SelectedField, Selection, SelectionId,
     },
     schema::{Schema, TypeId},
     type_qualifiers::GraphqlTypeQualifier,
@@ -18,6 +18,7 @@ use heck::*;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
 pub(crate) fn render_response_data_fields<'a>(
     operation_id: OperationId,
@@ -36,6 +37,11 @@ pub(crate) fn render_response_data_fields<'a>(
 
     let response_data_type_id = expanded_selection.push_type(ExpandedType {
         name: Cow::Borrowed("ResponseData"),
+        description: query
+            .schema
+            .get_object(operation.object_id)
+            .description
+            .as_deref(),
     });
 
     calculate_selection(
@@ -43,6 +49,7 @@ pub(crate) fn render_response_data_fields<'a>(
         &operation.selection_set,
         response_data_type_id,
         TypeId::Object(operation.object_id),
+        None,
         options,
     );
 
@@ -66,6 +73,7 @@ pub(super) fn render_fragment<'a>(
 
     let response_type_id = expanded_selection.push_type(ExpandedType {
         name: fragment.name.as_str().into(),
+        description: fragment.on.description(query.schema),
     });
 
     calculate_selection(
@@ -73,6 +81,7 @@ pub(super) fn render_fragment<'a>(
         &fragment.selection_set,
         response_type_id,
         fragment.on,
+        None,
         options,
     );
 
@@ -119,27 +128,32 @@ impl<'a> VariantSelection<'a> {
     }
 }
 
+/// `name_prefix` is the prefix of the names of the structs generated for the selection set. When
+/// it is `None`, they are named after the path of the selections in the query.
 fn calculate_selection<'a>(
     context: &mut ExpandedSelection<'a>,
     selection_set: &[SelectionId],
     struct_id: ResponseTypeId,
     type_id: TypeId,
+    name_prefix: Option<&str>,
     options: &'a GraphQLClientCodegenOptions,
 ) {
-    // If the selection only contains a fragment, replace the selection with
-    // that fragment.
-    if selection_set.len() == 1 {
-        if let Selection::FragmentSpread(fragment_id) =
-            context.query.query.get_selection(selection_set[0])
-        {
-            let fragment = context.query.query.get_fragment(*fragment_id);
-            context.push_type_alias(TypeAlias {
-                name: &fragment.name,
-                struct_id,
-                boxed: fragment_is_recursive(*fragment_id, context.query.query),
-            });
-            return;
-        }
+    // Inline fragments without a type condition, or on the type itself, are rendered in the same
+    // struct.
+    let selection_set = merge_inline_fragments(selection_set, type_id, context.query.query);
+    let selection_set = expand_overlapping_fragments(selection_set, type_id, context.query);
+    let selections = selection_set.selections.as_slice();
+
+    // If the selection only contains a fragment (possibly spread several times), replace the
+    // selection with that fragment.
+    if let Some(fragment_id) = single_fragment_spread(selections, context.query) {
+        let fragment = context.query.query.get_fragment(fragment_id);
+        context.push_type_alias(TypeAlias {
+            name: &fragment.name,
+            struct_id,
+            boxed: fragment_is_recursive(fragment_id, context.query.query),
+        });
+        return;
     }
 
     // If we are on a union or an interface, we need to generate an enum that matches the variants _exhaustively_.
@@ -164,7 +178,7 @@ fn calculate_selection<'a>(
 
         if let Some(variants) = variants {
             let variant_selections: Vec<(SelectionId, &Selection, VariantSelection<'_>)> =
-                selection_set
+                selections
                     .iter()
                     .map(|id| (id, context.query.query.get_selection(*id)))
                     .filter_map(|(id, selection)| {
@@ -186,26 +200,32 @@ fn calculate_selection<'a>(
                     })
                     .collect();
 
-                if let Some((selection_id, selection, _variant)) = variant_selections.get(0) {
+                if let Some((selection_id, _selection, _variant)) = variant_selections.get(0) {
                     let mut variant_struct_name_str =
-                        full_path_prefix(*selection_id, &context.query);
+                        selection_set.path_prefix(*selection_id, name_prefix, context.query);
                     variant_struct_name_str.reserve(2 + variant_name_str.len());
                     variant_struct_name_str.push_str("On");
                     variant_struct_name_str.push_str(variant_name_str);
 
+                    let variant_description = variant_type_id.description(context.schema());
+
                     context.push_variant(ExpandedVariant {
                         name: variant_name_str.into(),
                         variant_type: Some(variant_struct_name_str.clone().into()),
                         on: struct_id,
+                        description: variant_description,
                     });
 
                     let expanded_type = ExpandedType {
-                        name: variant_struct_name_str.into(),
+                        name: variant_struct_name_str.clone().into(),
+                        description: variant_description,
                     };
 
                     let struct_id = context.push_type(expanded_type);
 
-                    if variant_selections.len() == 1 {
+                    if variant_selections.len() == 1
+                        && !selection_set.is_conditional(variant_selections[0].0, context.query)
+                    {
                         if let VariantSelection::FragmentSpread((fragment_id, fragment)) =
                             variant_selections[0].2
                         {
@@ -218,28 +238,63 @@ fn calculate_selection<'a>(
                         }
                     }
 
+                    // All the inline fragments on the variant are merged into one struct.
+                    let inline_fragments_selection: Vec<SelectionId> = variant_selections
+                        .iter()
+                        .filter_map(
+                            |(_id, _selection, variant_selection)| match variant_selection {
+                                VariantSelection::InlineFragment(inline_fragment) => {
+                                    Some(inline_fragment.selection_set.iter().copied())
+                                }
+                                VariantSelection::FragmentSpread(_) => None,
+                            },
+                        )
+                        .flatten()
+                        .collect();
+
+                    if !inline_fragments_selection.is_empty() {
+                        let variant_name_prefix = selection_set.nested_name_prefix(
+                            variant_selections.iter().map(|(id, _, _)| *id),
+                            name_prefix,
+                            &variant_struct_name_str,
+                        );
+
+                        calculate_selection(
+                            context,
+                            &inline_fragments_selection,
+                            struct_id,
+                            *variant_type_id,
+                            variant_name_prefix,
+                            options,
+                        );
+                    }
+
+                    let mut rendered_fragments = HashSet::new();
+
                     for (_selection_id, _selection, variant_selection) in variant_selections {
-                        match variant_selection {
-                            VariantSelection::InlineFragment(_) => {
-                                calculate_selection(
-                                    context,
-                                    selection.subselection(),
-                                    struct_id,
-                                    *variant_type_id,
-                                    options,
-                                );
+                        if let VariantSelection::FragmentSpread((fragment_id, fragment)) =
+                            variant_selection
+                        {
+                            if !rendered_fragments.insert(*fragment_id) {
+                                continue;
                             }
-                            VariantSelection::FragmentSpread((fragment_id, fragment)) => context
-                                .push_field(ExpandedField {
-                                    field_type: fragment.name.as_str().into(),
-                                    field_type_qualifiers: &[GraphqlTypeQualifier::Required],
-                                    flatten: true,
-                                    graphql_name: None,
-                                    rust_name: fragment.name.to_snake_case().into(),
-                                    struct_id,
-                                    deprecation: None,
-                                    boxed: fragment_is_recursive(*fragment_id, context.query.query),
-                                }),
+
+                            context.push_field(ExpandedField {
+                                field_type: fragment.name.as_str().into(),
+                                field_type_qualifiers: &[GraphqlTypeQualifier::Required],
+                                flatten: true,
+                                graphql_name: None,
+                                rust_name: fragment.name.to_snake_case().into(),
+                                struct_id,
+                                deprecation: None,
+                                description: None,
+                                boxed: fragment_is_recursive(*fragment_id, context.query.query),
+                                conditional: fragment_spread_is_conditional(
+                                    *fragment_id,
+                                    &selection_set,
+                                    context.query,
+                                ),
+                            });
                         }
                     }
                 } else {
@@ -247,20 +302,33 @@ fn calculate_selection<'a>(
                         name: variant_name_str.into(),
                         on: struct_id,
                         variant_type: None,
+                        description: variant_type_id.description(context.schema()),
                     });
                 }
             }
         }
     }
 
-    for id in selection_set {
+    let mut rendered_response_keys: HashSet<&str> = HashSet::new();
+    let mut rendered_fragments: HashSet<ResolvedFragmentId> = HashSet::new();
+
+    for id in selections {
         let selection = context.query.query.get_selection(*id);
 
         match selection {
             Selection::Field(field) => {
+                let response_key = field.response_key(context.schema());
+
+                // A field can be selected several times, it is only rendered once. The validation
+                // guarantees that the selections are compatible.
+                if !rendered_response_keys.insert(response_key) {
+                    continue;
+                }
+
                 let (graphql_name, rust_name) = context.field_name(&field);
                 let schema_field = field.schema_field(context.schema());
                 let field_type_id = schema_field.r#type.id;
+                let conditional = field_is_conditional(response_key, &selection_set, context.query);
 
                 match field_type_id {
                     TypeId::Enum(enm) => {
@@ -274,7 +342,9 @@ fn calculate_selection<'a>(
                             field_type_qualifiers: &schema_field.r#type.qualifiers,
                             flatten: false,
                             deprecation: schema_field.deprecation(),
+                            description: schema_field.description.as_deref(),
                             boxed: false,
+                            conditional,
                         });
                     }
                     TypeId::Scalar(scalar) => {
@@ -291,11 +361,14 @@ fn calculate_selection<'a>(
                             rust_name,
                             flatten: false,
                             deprecation: schema_field.deprecation(),
+                            description: schema_field.description.as_deref(),
                             boxed: false,
+                            conditional,
                         });
                     }
                     TypeId::Object(_) | TypeId::Interface(_) | TypeId::Union(_) => {
-                        let struct_name_string = full_path_prefix(*id, &context.query);
+                        let struct_name_string =
+                            selection_set.path_prefix(*id, name_prefix, context.query);
 
                         context.push_field(ExpandedField {
                             struct_id,
@@ -306,17 +379,44 @@ fn calculate_selection<'a>(
                             flatten: false,
                             boxed: false,
                             deprecation: schema_field.deprecation(),
+                            description: schema_field.description.as_deref(),
+                            conditional,
                         });
 
                         let type_id = context.push_type(ExpandedType {
-                            name: Cow::Owned(struct_name_string),
+                            name: Cow::Owned(struct_name_string.clone()),
+                            description: field_type_id.description(context.schema()),
                         });
 
+                        let occurrences: Vec<SelectionId> = selections
+                            .iter()
+                            .copied()
+                            .filter(|id| match context.query.query.get_selection(*id) {
+                                Selection::Field(other_field) => {
+                                    other_field.response_key(context.schema()) == response_key
+                                }
+                                _ => false,
+                            })
+                            .collect();
+                        let merged_selection: Vec<SelectionId> = occurrences
+                            .iter()
+                            .filter_map(|id| {
+                                context.query.query.get_selection(*id).as_selected_field()
+                            })
+                            .flat_map(|field| field.selection_set.iter().copied())
+                            .collect();
+                        let field_name_prefix = selection_set.nested_name_prefix(
+                            occurrences.iter().copied(),
+                            name_prefix,
+                            &struct_name_string,
+                        );
+
                         calculate_selection(
                             context,
-                            selection.subselection(),
+                            &merged_selection,
                             type_id,
                             field_type_id,
+                            field_name_prefix,
                             options,
                         );
                     }
@@ -324,18 +424,20 @@ fn calculate_selection<'a>(
                 };
             }
             Selection::Typename => (),
+            // The remaining inline fragments are on variants, and were handled above.
             Selection::InlineFragment(_inline) => (),
             Selection::FragmentSpread(fragment_id) => {
-                // Here we only render fragments that are directly on the type
-                // itself, and not on one of its variants.
+                // Here we only render fragments that apply to the type as a
+                // whole, and not to one of its variants.
 
                 let fragment = context.query.query.get_fragment(*fragment_id);
 
                 // Assuming the query was validated properly, a fragment spread
-                // is either on the field's type itself, or on one of the
-                // variants (union or interfaces). If it's not directly a field
-                // on the struct, it will be handled in the `on` variants.
-                if fragment.on != type_id {
+                // on a union or an interface is either on the field's type
+                // itself, or on one of the variants. If it's not directly a
+                // field on the struct, it will be handled in the `on` variants.
+                if !fragment.is_flattened_into(type_id) || !rendered_fragments.insert(*fragment_id)
+                {
                     continue;
                 }
 
@@ -350,7 +452,13 @@ fn calculate_selection<'a>(
                     struct_id,
                     flatten: true,
                     deprecation: None,
+                    description: None,
                     boxed: fragment_is_recursive(*fragment_id, context.query.query),
+                    conditional: fragment_spread_is_conditional(
+                        *fragment_id,
+                        &selection_set,
+                        context.query,
+                    ),
                 });
 
                 // We stop here, because the structs for the fragments are generated separately, to
@@ -360,6 +468,218 @@ fn calculate_selection<'a>(
     }
 }
 
+/// A selection set where the spreads of flattened fragments that select a field also selected
+/// directly are replaced with the selections of the fragment. Such a field is then generated once,
+/// with the subselections of both, rather than both in the parent struct and in the fragment
+/// struct, where only one of them would be deserialized.
+struct ExpandedSelectionSet {
+    selections: Vec<SelectionId>,
+    /// The selections of expanded fragments, with the spread they replace in the original
+    /// selection set.
+    spreads: HashMap<SelectionId, SelectionId>,
+    /// The selections of expanded fragments that are only spread conditionally.
+    conditional: HashSet<SelectionId>,
+}
+
+impl ExpandedSelectionSet {
+    fn is_conditional(&self, selection_id: SelectionId, query: &BoundQuery<'_>) -> bool {
+        self.conditional.contains(&selection_id)
+            || query.query.selection_is_conditional(selection_id)
+    }
+
+    /// The prefix of the name of the structs generated for a selection of the set. Selections of
+    /// expanded fragments are named after the spread, so their structs do not collide with the
+    /// ones of the fragment.
+    fn path_prefix(
+        &self,
+        selection_id: SelectionId,
+        name_prefix: Option<&str>,
+        query: &BoundQuery<'_>,
+    ) -> String {
+        let mut prefix = match (name_prefix, self.spreads.get(&selection_id)) {
+            (Some(name_prefix), _) => name_prefix.to_owned(),
+            (None, Some(spread_id)) => full_path_prefix(*spread_id, query),
+            (None, None) => return full_path_prefix(selection_id, query),
+        };
+
+        if let selection @ Selection::Field(_) = query.query.get_selection(selection_id) {
+            prefix.push_str(&selection.to_path_segment(query));
+        }
+
+        prefix
+    }
+
+    /// The name prefix of the subselections of `selections`, generated in the struct named
+    /// `struct_name`: `None` if they can be named after their path in the query.
+    fn nested_name_prefix<'n>(
+        &self,
+        mut selections: impl Iterator<Item = SelectionId>,
+        name_prefix: Option<&str>,
+        struct_name: &'n str,
+    ) -> Option<&'n str> {
+        if name_prefix.is_some() || selections.any(|id| self.spreads.contains_key(&id)) {
+            Some(struct_name)
+        } else {
+            None
+        }
+    }
+}
+
+/// Expand the flattened fragments that select a field also selected directly, including through
+/// fragments expanded before, until there are none left.
+fn expand_overlapping_fragments(
+    selection_set: Vec<SelectionId>,
+    type_id: TypeId,
+    query: &BoundQuery<'_>,
+) -> ExpandedSelectionSet {
+    let mut expanded = ExpandedSelectionSet {
+        selections: selection_set,
+        spreads: HashMap::new(),
+        conditional: HashSet::new(),
+    };
+    let mut expanded_fragments: Vec<ResolvedFragmentId> = Vec::new();
+
+    loop {
+        let overlapping_fragment = expanded.selections.iter().find_map(|id| {
+            let fragment_id = match query.query.get_selection(*id) {
+                Selection::FragmentSpread(fragment_id) => *fragment_id,
+                _ => return None,
+            };
+
+            if expanded_fragments.contains(&fragment_id)
+                || !query
+                    .query
+                    .get_fragment(fragment_id)
+                    .is_flattened_into(type_id)
+            {
+                return None;
+            }
+
+            let overlaps = expanded
+                .selections
+                .iter()
+                .filter_map(|id| query.query.get_selection(*id).as_selected_field())
+                .any(|field| {
+                    fragment_field_by_response_key(
+                        fragment_id,
+                        field.response_key(query.schema),
+                        query,
+                    )
+                    .is_some()
+                });
+
+            if overlaps {
+                Some(fragment_id)
+            } else {
+                None
+            }
+        });
+
+        let fragment_id = match overlapping_fragment {
+            Some(fragment_id) => fragment_id,
+            None => return expanded,
+        };
+        expanded_fragments.push(fragment_id);
+
+        let spread_ids: Vec<SelectionId> = expanded
+            .selections
+            .iter()
+            .copied()
+            .filter(|id| {
+                matches!(query.query.get_selection(*id), Selection::FragmentSpread(id) if *id == fragment_id)
+            })
+            .collect();
+        let first_spread_id = spread_ids[0];
+        // Spreads from fragments expanded earlier stand for the spread in the original set.
+        let spread_id = expanded
+            .spreads
+            .get(&first_spread_id)
+            .copied()
+            .unwrap_or(first_spread_id);
+        let conditional = spread_ids
+            .iter()
+            .all(|id| expanded.is_conditional(*id, query));
+        let fragment = query.query.get_fragment(fragment_id);
+        let fragment_selections =
+            merge_inline_fragments(&fragment.selection_set, type_id, query.query);
+
+        for id in &fragment_selections {
+            expanded.spreads.insert(*id, spread_id);
+
+            if conditional {
+                expanded.conditional.insert(*id);
+            }
+        }
+
+        // The fragment is expanded in place of its first spread, the other spreads are dropped.
+        let position = expanded
+            .selections
+            .iter()
+            .position(|id| *id == first_spread_id)
+            .expect("spread of the expanded fragment");
+        expanded.selections.retain(|id| !spread_ids.contains(id));
+        expanded
+            .selections
+            .splice(position..position, fragment_selections);
+    }
+}
+
+/// Whether every selection of the field with this response key in the set is conditional.
+fn field_is_conditional(
+    response_key: &str,
+    selection_set: &ExpandedSelectionSet,
+    query: &BoundQuery<'_>,
+) -> bool {
+    selection_set
+        .selections
+        .iter()
+        .filter(|id| match query.query.get_selection(**id) {
+            Selection::Field(field) => field.response_key(query.schema) == response_key,
+            _ => false,
+        })
+        .all(|id| selection_set.is_conditional(*id, query))
+}
+
+/// Whether every spread of the fragment in the set is conditional.
+fn fragment_spread_is_conditional(
+    fragment_id: ResolvedFragmentId,
+    selection_set: &ExpandedSelectionSet,
+    query: &BoundQuery<'_>,
+) -> bool {
+    selection_set
+        .selections
+        .iter()
+        .filter(|id| {
+            matches!(query.query.get_selection(**id), Selection::FragmentSpread(id) if *id == fragment_id)
+        })
+        .all(|id| selection_set.is_conditional(*id, query))
+}
+
+/// The fragment, if the selection set only consists of unconditional spreads of that fragment.
+fn single_fragment_spread(
+    selection_set: &[SelectionId],
+    query: &BoundQuery<'_>,
+) -> Option<ResolvedFragmentId> {
+    let mut fragment_ids = selection_set
+        .iter()
+        .map(|id| match query.query.get_selection(*id) {
+            Selection::FragmentSpread(fragment_id)
+                if !query.query.selection_is_conditional(*id) =>
+            {
+                Some(*fragment_id)
+            }
+            _ => None,
+        });
+
+    let first = fragment_ids.next()??;
+
+    if fragment_ids.all(|fragment_id| fragment_id == Some(first)) {
+        Some(first)
+    } else {
+        None
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 struct ResponseTypeId(u32);
 
@@ -377,15 +697,28 @@ struct ExpandedField<'a> {
     struct_id: ResponseTypeId,
     flatten: bool,
     deprecation: Option<Option<&'a str>>,
+    description: Option<&'a str>,
     boxed: bool,
+    /// The field is under a `@skip` or `@include` directive, so it may be absent from the
+    /// response.
+    conditional: bool,
 }
 
 impl<'a> ExpandedField<'a> {
-    fn render(&self, options: &GraphQLClientCodegenOptions) -> Option<TokenStream> {
+    fn render(&self, options: &GraphQLClientCodegenOptions) -> TokenStream {
         let ident = Ident::new(&self.rust_name, Span::call_site());
+
+        // Conditional fields are always optional, whatever their type in the schema.
+        let field_type_qualifiers = match self.field_type_qualifiers.split_first() {
+            Some((GraphqlTypeQualifier::Required, nullable_qualifiers)) if self.conditional => {
+                nullable_qualifiers
+            }
+            _ => self.field_type_qualifiers,
+        };
+
         let qualified_type = decorate_type(
             &Ident::new(&self.field_type, Span::call_site()),
-            self.field_type_qualifiers,
+            field_type_qualifiers,
         );
 
         let qualified_type = if self.boxed {
@@ -403,26 +736,25 @@ impl<'a> ExpandedField<'a> {
         } else {
             None
         };
+        // A flattened `Option` is `None` when the fragment is absent, other fields need a default.
+        let optional_default = if self.conditional && !self.flatten {
+            Some(quote!(#[serde(default)]))
+        } else {
+            None
+        };
 
         let optional_deprecation_annotation =
-            match (self.deprecation, options.deprecation_strategy()) {
-                (None, _) | (Some(_), DeprecationStrategy::Allow) => None,
-                (Some(msg), DeprecationStrategy::Warn) => {
-                    let optional_msg = msg.map(|msg| quote!((note = #msg)));
-
-                    Some(quote!(#[deprecated#optional_msg]))
-                }
-                (Some(_), DeprecationStrategy::Deny) => return None,
-            };
+            deprecation_annotation(self.deprecation, &options.deprecation_strategy());
+        let doc = doc_comment(self.description);
 
-        let tokens = quote! {
+        quote! {
+            #doc
             #optional_flatten
+            #optional_default
             #optional_rename
             #optional_deprecation_annotation
             pub #ident: #qualified_type
-        };
-
-        Some(tokens)
+        }
     }
 }
 
@@ -430,6 +762,7 @@ struct ExpandedVariant<'a> {
     name: Cow<'a, str>,
     variant_type: Option<Cow<'a, str>>,
     on: ResponseTypeId,
+    description: Option<&'a str>,
 }
 
 impl<'a> ExpandedVariant<'a> {
@@ -440,12 +773,16 @@ impl<'a> ExpandedVariant<'a> {
             quote!((#ident))
         });
 
-        quote!(#name_ident #optional_type_ident)
+        let doc = doc_comment(self.description);
+
+        quote!(#doc #name_ident #optional_type_ident)
     }
 }
 
 pub(crate) struct ExpandedType<'a> {
     name: Cow<'a, str>,
+    /// The description of the GraphQL type the struct is a selection on.
+    description: Option<&'a str>,
 }
 
 pub(crate) struct ExpandedSelection<'a> {
@@ -504,6 +841,7 @@ impl<'a> ExpandedSelection<'a> {
 
         for (type_id, ty) in self.types() {
             let struct_name = Ident::new(&ty.name, Span::call_site());
+            let doc = doc_comment(ty.description);
 
             // If the type is aliased, stop here.
             if let Some(alias) = self.aliases.iter().find(|alias| alias.struct_id == type_id) {
@@ -524,7 +862,7 @@ impl<'a> ExpandedSelection<'a> {
                 .fields
                 .iter()
                 .filter(|field| field.struct_id == type_id)
-                .filter_map(|field| field.render(self.options))
+                .map(|field| field.render(self.options))
                 .peekable();
 
             let on_variants: Vec<TokenStream> = self
@@ -538,6 +876,7 @@ impl<'a> ExpandedSelection<'a> {
             // of the variants.
             if fields.peek().is_none() {
                 let item = quote! {
+                    #doc
                     #response_derives
                     #[serde(tag = "__typename")]
                     pub enum #struct_name {
@@ -567,6 +906,7 @@ impl<'a> ExpandedSelection<'a> {
             };
 
             let tokens = quote! {
+                #doc
                 #response_derives
                 pub struct #struct_name {
                     #(#fields,)*
@@ -1,3 +1,4 @@
+use crate::deprecation::DeprecationStrategy;
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::borrow::Cow;
@@ -84,6 +85,57 @@ pub(crate) fn field_rename_annotation(graphql_name: &str, rust_name: &str) -> Op
     }
 }
 
+/// Render a description from the schema as doc comments, if there is one. Descriptions are
+/// Markdown like doc comments, but code blocks without a language would be compiled as doctests,
+/// so they are marked as `text`.
+pub(crate) fn doc_comment(description: Option<&str>) -> Option<TokenStream> {
+    let description = description?.trim();
+
+    if description.is_empty() {
+        return None;
+    }
+
+    let mut in_code_block = false;
+    let lines = description.lines().map(|line| {
+        let line = line.trim_end();
+        let fence = line.trim_start();
+
+        if fence.starts_with("```") {
+            let opens_code_block = !in_code_block;
+            in_code_block = !in_code_block;
+
+            if opens_code_block && fence.trim_start_matches('`').is_empty() {
+                return format!(" {}text", line);
+            }
+        }
+
+        if line.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", line)
+        }
+    });
+
+    Some(quote!(#(#[doc = #lines])*))
+}
+
+/// The `#[deprecated]` annotation of a field, enum value, input field or variable under a
+/// deprecation strategy, if any. Only `warn` annotates: with `deny`, using deprecated items in a
+/// query is a validation error instead.
+pub(crate) fn deprecation_annotation(
+    deprecation: Option<Option<&str>>,
+    strategy: &DeprecationStrategy,
+) -> Option<TokenStream> {
+    match (deprecation, strategy) {
+        (Some(msg), DeprecationStrategy::Warn) => {
+            let optional_msg = msg.map(|msg| quote!((note = #msg)));
+
+            Some(quote!(#[deprecated#optional_msg]))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -94,4 +146,29 @@ mod tests {
         assert_eq!("fn_", keyword_replace("fn"));
         assert_eq!("struct_", keyword_replace("struct"));
     }
+
+    #[test]
+    fn doc_comment_works() {
+        use super::doc_comment;
+        assert!(doc_comment(None).is_none());
+        assert!(doc_comment(Some("  \n")).is_none());
+        assert_eq!(
+            doc_comment(Some(
+                "A user.\n\n```\nquery { me }\n```\n```graphql\n{ me }\n```\n"
+            ))
+            .unwrap()
+            .to_string(),
+            quote::quote! {
+                #[doc = " A user."]
+                #[doc = ""]
+                #[doc = " ```text"]
+                #[doc = " query { me }"]
+                #[doc = " ```"]
+                #[doc = " ```graphql"]
+                #[doc = " { me }"]
+                #[doc = " ```"]
+            }
+            .to_string()
+        );
+    }
 }
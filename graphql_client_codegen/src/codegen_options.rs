@@ -1,6 +1,8 @@
 use crate::deprecation::DeprecationStrategy;
 use crate::normalization::Normalization;
+use crate::scalar_presets::ScalarPreset;
 use proc_macro2::Ident;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 use syn::Visibility;
 
@@ -39,6 +41,16 @@ pub struct GraphQLClientCodegenOptions {
     schema_file: Option<PathBuf>,
     /// Normalization pattern for query types and names.
     normalization: Normalization,
+    /// The Rust types of custom scalars, by GraphQL name.
+    custom_scalars: BTreeMap<String, String>,
+    /// The presets mapping common custom scalars to Rust types.
+    scalar_presets: Vec<ScalarPreset>,
+    /// The enums defined in the parent module instead of generated, by GraphQL name.
+    extern_enums: BTreeSet<String>,
+    /// The enums generated without the `Other(String)` variant, by GraphQL name.
+    closed_enums: BTreeSet<String>,
+    /// Whether all the enums are generated without the `Other(String)` variant.
+    all_enums_closed: bool,
 }
 
 impl GraphQLClientCodegenOptions {
@@ -56,6 +68,11 @@ impl GraphQLClientCodegenOptions {
             query_file: Default::default(),
             schema_file: Default::default(),
             normalization: Normalization::None,
+            custom_scalars: BTreeMap::new(),
+            scalar_presets: Vec::new(),
+            extern_enums: BTreeSet::new(),
+            closed_enums: BTreeSet::new(),
+            all_enums_closed: false,
         }
     }
 
@@ -174,4 +191,82 @@ impl GraphQLClientCodegenOptions {
     pub fn normalization(&self) -> &Normalization {
         &self.normalization
     }
+
+    /// Map a custom scalar to a Rust type, e.g. `DateTime` to `chrono::DateTime<chrono::Utc>`.
+    ///
+    /// Once a scalar is mapped, every custom scalar used by the query must be mapped. Without any
+    /// mapping, the generated code refers to type aliases with the names of the scalars in the
+    /// parent module.
+    ///
+    /// The built-in `Int`, `Float` and `ID` scalars can be mapped as well, e.g. `Int` to `i32`. They
+    /// default to `i64`, `f64` and `String`.
+    pub fn set_custom_scalar(&mut self, graphql_name: String, rust_type: String) {
+        self.custom_scalars.insert(graphql_name, rust_type);
+    }
+
+    /// The Rust type a custom scalar is mapped to, if any.
+    pub(crate) fn custom_scalar(&self, graphql_name: &str) -> Option<&str> {
+        self.custom_scalars.get(graphql_name).map(String::as_str)
+    }
+
+    /// Map the common custom scalars of a preset, e.g. `DateTime` for `ScalarPreset::Chrono`, to
+    /// the types of its crate, re-exported by `graphql_client` with the matching `scalars-*`
+    /// feature. Scalars mapped with `set_custom_scalar` keep their mapping.
+    pub fn set_scalar_preset(&mut self, preset: ScalarPreset) {
+        if !self.scalar_presets.contains(&preset) {
+            self.scalar_presets.push(preset);
+        }
+    }
+
+    /// The Rust type of a custom scalar in the enabled presets, if any.
+    pub(crate) fn scalar_preset_type(&self, graphql_name: &str) -> Option<&'static str> {
+        self.scalar_presets
+            .iter()
+            .find_map(|preset| preset.scalar_type(graphql_name))
+    }
+
+    /// Use an enum from the parent module instead of generating one, e.g. to share a domain enum
+    /// between operations. The enum must have the name the generated enum would have, and implement
+    /// `Serialize` and `Deserialize` itself.
+    pub fn set_extern_enum(&mut self, graphql_name: String) {
+        self.extern_enums.insert(graphql_name);
+    }
+
+    /// The enums defined in the parent module, by GraphQL name.
+    pub(crate) fn extern_enums(&self) -> impl Iterator<Item = &str> {
+        self.extern_enums.iter().map(String::as_str)
+    }
+
+    /// Whether the enum is defined in the parent module instead of generated.
+    pub(crate) fn is_extern_enum(&self, graphql_name: &str) -> bool {
+        self.extern_enums.contains(graphql_name)
+    }
+
+    /// Generate the enum without the `Other(String)` variant. Deserializing a value that is not in
+    /// the schema is then an error, and the enum derives `Copy`, `Eq` and `Hash`.
+    pub fn set_closed_enum(&mut self, graphql_name: String) {
+        self.closed_enums.insert(graphql_name);
+    }
+
+    /// Generate all the enums without the `Other(String)` variant, see `set_closed_enum`.
+    pub fn set_all_enums_closed(&mut self) {
+        self.all_enums_closed = true;
+    }
+
+    /// Whether the enum is generated without the `Other(String)` variant.
+    pub(crate) fn is_closed_enum(&self, graphql_name: &str) -> bool {
+        self.all_enums_closed || self.closed_enums.contains(graphql_name)
+    }
+
+    /// The enums generated without the `Other(String)` variant, by GraphQL name.
+    pub(crate) fn closed_enums(&self) -> impl Iterator<Item = &str> {
+        self.closed_enums.iter().map(String::as_str)
+    }
+
+    /// Whether any custom scalar is mapped to a Rust type. Mappings of built-in scalars do not count.
+    pub(crate) fn has_custom_scalars(&self) -> bool {
+        self.custom_scalars
+            .keys()
+            .any(|name| !crate::schema::DEFAULT_SCALARS.contains(&name.as_str()))
+    }
 }
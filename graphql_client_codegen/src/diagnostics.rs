@@ -0,0 +1,91 @@
+use graphql_parser::Pos;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Render an error in a GraphQL document as an annotated snippet of the document, in the style of
+/// rustc diagnostics:
+///
+/// ```text
+/// error: No field named nickname on Character
+///  --> src/queries/hero.graphql:2:10
+///   |
+/// 2 |   hero { nickname }
+///   |          ^^^^^^^^
+/// ```
+///
+/// The `path:line:column` line lets editors and terminals link to the error.
+pub(crate) fn render_snippet(
+    path: &Path,
+    source: &str,
+    position: Option<Pos>,
+    message: &str,
+) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "error: {}", message).expect("write to a String");
+
+    let position = match position {
+        Some(position) => position,
+        None => {
+            write!(out, " --> {}", path.display()).expect("write to a String");
+            return out;
+        }
+    };
+
+    let line = source
+        .lines()
+        .nth(position.line.saturating_sub(1))
+        .unwrap_or_default();
+    let line_number = position.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    // The parser counts tabs as 8 columns.
+    let mut width = 0;
+    let column = line
+        .chars()
+        .take_while(|c| {
+            width += if *c == '\t' { 8 } else { 1 };
+            width < position.column
+        })
+        .count();
+
+    // Keep the tabs, so that the carets line up with the source line.
+    let padding: String = line
+        .chars()
+        .take(column)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let token: String = line.chars().skip(column).collect();
+
+    write!(
+        out,
+        "{gutter}--> {path}:{line}:{column}\n\
+         {gutter} |\n\
+         {line_number} | {source_line}\n\
+         {gutter} | {padding}{carets}",
+        gutter = gutter,
+        path = path.display(),
+        line = position.line,
+        column = position.column,
+        line_number = line_number,
+        source_line = line,
+        padding = padding,
+        carets = "^".repeat(token_width(&token)),
+    )
+    .expect("write to a String");
+
+    out
+}
+
+/// The width of the token at the start of `text`: a name or a variable. Other tokens are one
+/// character wide.
+fn token_width(text: &str) -> usize {
+    let prefix_width = if text.starts_with('$') { 1 } else { 0 };
+
+    let name_width = text[prefix_width..]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .count();
+
+    (prefix_width + name_width).max(1)
+}
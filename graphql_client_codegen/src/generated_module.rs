@@ -1,6 +1,6 @@
 use crate::{
     codegen_options::*,
-    query::{BoundQuery, OperationId},
+    query::{BoundQuery, OperationId, OperationType},
     BoxError,
 };
 use heck::*;
@@ -53,7 +53,13 @@ impl<'a> GeneratedModule<'a> {
     pub(crate) fn to_token_stream(&self) -> Result<TokenStream, BoxError> {
         let module_name = Ident::new(&self.operation.to_snake_case(), Span::call_site());
         let module_visibility = &self.options.module_visibility();
-        let operation_name = self.operation;
+        let operation = self.resolved_query.get_operation(self.root()?);
+        // Anonymous operations are sent without an operation name.
+        let operation_name = if operation.anonymous {
+            ""
+        } else {
+            self.operation
+        };
         let operation_name_ident = self.options.normalization().operation(self.operation);
         let operation_name_ident = Ident::new(&operation_name_ident, Span::call_site());
 
@@ -72,8 +78,18 @@ impl<'a> GeneratedModule<'a> {
         let query_string = &self.query_string;
         let impls = self.build_impls()?;
 
+        let operation_type = match operation.operation_type {
+            OperationType::Query => quote!(Query),
+            OperationType::Mutation => quote!(Mutation),
+            OperationType::Subscription => quote!(Subscription),
+        };
+
         let struct_declaration: Option<_> = match self.options.mode {
-            CodegenMode::Cli => Some(quote!(#module_visibility struct #operation_name_ident;)),
+            CodegenMode::Cli => {
+                let doc = format!(" The `{}` operation.", operation.name);
+
+                Some(quote!(#[doc = #doc] #module_visibility struct #operation_name_ident;))
+            }
             // The struct is already present in derive mode.
             CodegenMode::Derive => None,
         };
@@ -82,7 +98,8 @@ impl<'a> GeneratedModule<'a> {
             #struct_declaration
 
             #module_visibility mod #module_name {
-                #![allow(dead_code)]
+                // Only the items with a description in the schema are documented.
+                #![allow(dead_code, missing_docs)]
 
                 use std::result::Result;
 
@@ -98,6 +115,9 @@ impl<'a> GeneratedModule<'a> {
                 type Variables = #module_name::Variables;
                 type ResponseData = #module_name::ResponseData;
 
+                const OPERATION_TYPE: Option<::graphql_client::OperationType> =
+                    Some(::graphql_client::OperationType::#operation_type);
+
                 fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
                     graphql_client::QueryBody {
                         variables,
@@ -6,6 +6,7 @@
 //!
 //! It is not meant to be used directly by users of the library.
 
+use heck::CamelCase;
 use lazy_static::*;
 use proc_macro2::TokenStream;
 use quote::*;
@@ -18,10 +19,13 @@ pub mod deprecation;
 pub mod schema;
 
 mod constants;
+mod diagnostics;
 mod generated_module;
 /// Normalization-related code
 pub mod normalization;
 mod query;
+/// Rust types for common custom scalars.
+pub mod scalar_presets;
 mod type_qualifiers;
 
 #[cfg(test)]
@@ -67,11 +71,11 @@ pub fn generate_module_token_stream(
                 let schema_string = read_file(v.key())?;
                 let schema = match schema_extension {
                     "graphql" | "gql" => {
-                        let s = graphql_parser::schema::parse_schema(&schema_string).map_err(|parser_error| GeneralError(format!("Parser error: {}", parser_error)))?;
+                        let s = graphql_parser::schema::parse_schema(&schema_string).map_err(|parser_error| GeneralError(format!("Schema parser error in {}: {}", v.key().display(), parser_error)))?;
                         schema::Schema::from(s)
                     }
                     "json" => {
-                        let parsed: graphql_introspection_query::introspection_response::IntrospectionResponse = serde_json::from_str(&schema_string)?;
+                        let parsed: graphql_introspection_query::introspection_response::IntrospectionResponse = serde_json::from_str(&schema_string).map_err(|err| GeneralError(format!("Schema parser error in {}:{}:{}: {}", v.key().display(), err.line(), err.column(), err)))?;
                         schema::Schema::from(parsed)
                     }
                     extension => return Err(GeneralError(format!("Unsupported extension for the GraphQL schema: {} (only .json and .graphql are supported)", extension)).into())
@@ -82,21 +86,40 @@ pub fn generate_module_token_stream(
         }
     };
 
+    // An anonymous operation is named after the struct under derive, or after the query file.
+    let anonymous_operation_name = match options.mode {
+        CodegenMode::Derive => options
+            .struct_ident()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        CodegenMode::Cli => query_path
+            .file_stem()
+            .and_then(std::ffi::OsStr::to_str)
+            .unwrap_or_default()
+            .to_camel_case(),
+    };
+
     // We need to qualify the query with the path to the crate it is part of
     let (query_string, query) = {
         let mut lock = QUERY_CACHE.lock().expect("query cache is poisoned");
-        match lock.entry(query_path) {
+        match lock.entry(query_path.clone()) {
             hash_map::Entry::Occupied(o) => o.get().clone(),
             hash_map::Entry::Vacant(v) => {
                 let query_string = read_file(v.key())?;
-                let query = graphql_parser::parse_query(&query_string)
-                    .map_err(|err| GeneralError(format!("Query parser error: {}", err)))?;
+                let query = graphql_parser::parse_query(&query_string).map_err(|err| {
+                    GeneralError(format!(
+                        "Query parser error in {}: {}",
+                        v.key().display(),
+                        err
+                    ))
+                })?;
                 v.insert((query_string, query)).clone()
             }
         }
     };
 
-    let query = crate::query::resolve(&schema, &query)?;
+    let query = crate::query::resolve(&schema, &query, &anonymous_operation_name)
+        .map_err(|errors| errors.in_document(&query_path, &query_string))?;
 
     // Determine which operation we are generating code for. This will be used in operationName.
     let operations = options
@@ -117,6 +140,16 @@ pub fn generate_module_token_stream(
         }
     };
 
+    if options.deprecation_strategy() == deprecation::DeprecationStrategy::Deny {
+        let bound_query = query::BoundQuery {
+            query: &query,
+            schema: &schema,
+        };
+
+        query::validate_deprecations(operations.iter().map(|(id, _)| *id), &bound_query)
+            .map_err(|errors| errors.in_document(&query_path, &query_string))?;
+    }
+
     // The generated modules.
     let mut modules = Vec::with_capacity(operations.len());
 
@@ -140,7 +173,7 @@ pub fn generate_module_token_stream(
 #[derive(Debug, Error)]
 enum ReadFileError {
     #[error(
-        "Could not find file with path: {}\
+        "Could not find file with path: {}\n\
         Hint: file paths in the GraphQLQuery attribute are relative to the project root (location of the Cargo.toml). Example: query_path = \"src/my_query.graphql\".",
         path
     )]
@@ -6,30 +6,93 @@ mod operations;
 mod selection;
 mod validation;
 
-pub(crate) use fragments::{fragment_is_recursive, ResolvedFragment};
-pub(crate) use operations::ResolvedOperation;
+#[cfg(test)]
+mod tests;
+
+pub(crate) use fragments::{
+    fragment_field_by_response_key, fragment_is_recursive, ResolvedFragment,
+};
+pub(crate) use operations::{OperationType, ResolvedOperation};
 pub(crate) use selection::*;
 
 use crate::{
     constants::TYPENAME_FIELD,
+    diagnostics::render_snippet,
     normalization::Normalization,
     schema::{
         resolve_field_type, EnumId, InputId, ScalarId, Schema, StoredEnum, StoredFieldType,
         StoredInputType, StoredScalar, TypeId, UnionId,
     },
 };
+use graphql_parser::Pos;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 #[error("{}", message)]
 pub(crate) struct QueryValidationError {
     message: String,
+    /// Where the error is in the query document, when it is known.
+    position: Option<Pos>,
 }
 
 impl QueryValidationError {
     pub(crate) fn new(message: String) -> Self {
-        QueryValidationError { message }
+        QueryValidationError {
+            message,
+            position: None,
+        }
+    }
+
+    pub(crate) fn at(mut self, position: Pos) -> Self {
+        self.position = Some(position);
+        self
+    }
+}
+
+/// All the errors found in a query document, in the order they were found.
+#[derive(Debug, Error)]
+pub(crate) struct QueryValidationErrors {
+    errors: Vec<QueryValidationError>,
+    /// The path and the contents of the document, to render the errors as snippets of it.
+    document: Option<(PathBuf, String)>,
+}
+
+impl QueryValidationErrors {
+    pub(crate) fn in_document(mut self, path: &Path, source: &str) -> Self {
+        self.document = Some((path.to_path_buf(), source.to_owned()));
+        self
+    }
+}
+
+impl fmt::Display for QueryValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, error) in self.errors.iter().enumerate() {
+            if idx > 0 {
+                // The snippets are separated by a blank line.
+                f.write_str(if self.document.is_some() {
+                    "\n\n"
+                } else {
+                    "\n"
+                })?;
+            }
+
+            match (&self.document, error.position) {
+                (Some((path, source)), position) => {
+                    f.write_str(&render_snippet(path, source, position, &error.message))?
+                }
+                (None, Some(position)) => write!(
+                    f,
+                    "line {}, column {}: {}",
+                    position.line, position.column, error.message
+                )?,
+                (None, None) => f.write_str(&error.message)?,
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -50,80 +113,129 @@ pub(crate) struct ResolvedFragmentId(u32);
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct VariableId(u32);
 
+/// Resolve and validate a query document. `anonymous_operation_name` is the name given to the
+/// operation of the document if it is anonymous.
+///
+/// All the errors in the document are reported together. Validation only runs on documents that
+/// could be resolved against the schema, so that the errors do not cascade.
 pub(crate) fn resolve(
     schema: &Schema,
     query: &graphql_parser::query::Document,
-) -> Result<Query, QueryValidationError> {
+    anonymous_operation_name: &str,
+) -> Result<Query, QueryValidationErrors> {
     let mut resolved_query: Query = Default::default();
 
-    create_roots(&mut resolved_query, query, schema)?;
+    create_roots(&mut resolved_query, query, schema, anonymous_operation_name);
 
     // Then resolve the selections.
     for definition in &query.definitions {
         match definition {
             graphql_parser::query::Definition::Fragment(fragment) => {
-                resolve_fragment(&mut resolved_query, schema, fragment)?
-            }
-            graphql_parser::query::Definition::Operation(operation) => {
-                resolve_operation(&mut resolved_query, schema, operation)?
+                resolve_fragment(&mut resolved_query, schema, fragment)
             }
+            graphql_parser::query::Definition::Operation(operation) => resolve_operation(
+                &mut resolved_query,
+                schema,
+                operation,
+                anonymous_operation_name,
+            ),
         }
     }
 
-    // Validation: to be expanded and factored out.
-    validation::validate_typename_presence(&BoundQuery {
+    let mut errors = std::mem::take(&mut resolved_query.errors);
+    let bound_query = BoundQuery {
         query: &resolved_query,
         schema,
-    })?;
+    };
 
-    for (selection_id, _) in resolved_query.selections() {
-        selection::validate_type_conditions(
-            selection_id,
-            &BoundQuery {
-                query: &resolved_query,
-                schema,
-            },
-        )?
+    // The other validations assume that fragments do not spread themselves.
+    if errors.is_empty() {
+        validation::validate_fragment_cycles(&bound_query, &mut errors);
+    }
+
+    if errors.is_empty() {
+        validation::validate_unused_fragments(&bound_query, &mut errors);
+        validation::validate_typename_presence(&bound_query, &mut errors);
+        validation::validate_arguments(&bound_query, &mut errors);
+        validation::validate_variables(&bound_query, &mut errors);
+        validation::validate_overlapping_fields(&bound_query, &mut errors);
+
+        for (selection_id, _) in resolved_query.selections() {
+            selection::validate_type_conditions(selection_id, &bound_query, &mut errors);
+        }
     }
 
-    Ok(resolved_query)
+    if errors.is_empty() {
+        Ok(resolved_query)
+    } else {
+        Err(QueryValidationErrors {
+            errors,
+            document: None,
+        })
+    }
 }
 
 fn create_roots(
     resolved_query: &mut Query,
     query: &graphql_parser::query::Document,
     schema: &Schema,
-) -> Result<(), QueryValidationError> {
+    anonymous_operation_name: &str,
+) {
+    let operation_name = |name: &Option<String>| {
+        name.as_deref()
+            .unwrap_or(anonymous_operation_name)
+            .to_owned()
+    };
+
     // First, give ids to all fragments and operations.
     for definition in &query.definitions {
         match definition {
             graphql_parser::query::Definition::Fragment(fragment) => {
                 let graphql_parser::query::TypeCondition::On(on) = &fragment.type_condition;
+                let on = match schema.find_type(on) {
+                    Some(on) => on,
+                    None => {
+                        resolved_query.errors.push(
+                            QueryValidationError::new(format!(
+                                "Could not find type {} for fragment {} in schema.",
+                                on, fragment.name
+                            ))
+                            .at(fragment.position),
+                        );
+                        continue;
+                    }
+                };
+
                 resolved_query.fragments.push(ResolvedFragment {
                     name: fragment.name.clone(),
-                    on: schema.find_type(on).ok_or_else(|| {
-                        QueryValidationError::new(format!(
-                            "Could not find type {} for fragment {} in schema.",
-                            on, fragment.name
-                        ))
-                    })?,
+                    on,
                     selection_set: Vec::new(),
+                    position: fragment.position,
                 });
             }
             graphql_parser::query::Definition::Operation(
                 graphql_parser::query::OperationDefinition::Mutation(m),
             ) => {
-                let on = schema.mutation_type().ok_or_else(|| {
-                    QueryValidationError::new(
-                        "Query contains a mutation operation, but the schema has no mutation type."
-                            .to_owned(),
-                    )
-                })?;
+                let on = match schema.mutation_type() {
+                    Some(on) => on,
+                    None => {
+                        resolved_query.errors.push(
+                            QueryValidationError::new(
+                                "Query contains a mutation operation, but the schema has no mutation type."
+                                    .to_owned(),
+                            )
+                            .at(m.position),
+                        );
+                        continue;
+                    }
+                };
                 let resolved_operation: ResolvedOperation = ResolvedOperation {
                     object_id: on,
-                    name: m.name.as_ref().expect("mutation without name").to_owned(),
-                    _operation_type: operations::OperationType::Mutation,
+                    name: operation_name(&m.name),
+                    anonymous: m.name.is_none(),
+                    operation_type: operations::OperationType::Mutation,
                     selection_set: Vec::with_capacity(m.selection_set.items.len()),
+                    position: m.position,
                 };
 
                 resolved_query.operations.push(resolved_operation);
@@ -133,10 +245,12 @@ fn create_roots(
             ) => {
                 let on = schema.query_type();
                 let resolved_operation: ResolvedOperation = ResolvedOperation {
-                    name: q.name.as_ref().expect("query without name").to_owned(),
-                    _operation_type: operations::OperationType::Query,
+                    name: operation_name(&q.name),
+                    anonymous: q.name.is_none(),
+                    operation_type: operations::OperationType::Query,
                     object_id: on,
                     selection_set: Vec::with_capacity(q.selection_set.items.len()),
+                    position: q.position,
                 };
 
                 resolved_query.operations.push(resolved_operation);
@@ -144,65 +258,88 @@ fn create_roots(
             graphql_parser::query::Definition::Operation(
                 graphql_parser::query::OperationDefinition::Subscription(s),
             ) => {
-                let on = schema.subscription_type().ok_or_else(|| {
-                    QueryValidationError::new(
-                        "Query contains a subscription operation, but the schema has no subscription type.".to_owned()
-                    )
-                })?;
+                let on = match schema.subscription_type() {
+                    Some(on) => on,
+                    None => {
+                        resolved_query.errors.push(
+                            QueryValidationError::new(
+                                "Query contains a subscription operation, but the schema has no subscription type.".to_owned()
+                            )
+                            .at(s.position),
+                        );
+                        continue;
+                    }
+                };
 
                 if s.selection_set.items.len() != 1 {
-                    return Err(QueryValidationError::new(
-                        crate::constants::MULTIPLE_SUBSCRIPTION_FIELDS_ERROR.to_owned(),
-                    ));
+                    resolved_query.errors.push(
+                        QueryValidationError::new(
+                            crate::constants::MULTIPLE_SUBSCRIPTION_FIELDS_ERROR.to_owned(),
+                        )
+                        .at(s.position),
+                    );
                 }
 
                 let resolved_operation: ResolvedOperation = ResolvedOperation {
-                    name: s
-                        .name
-                        .as_ref()
-                        .expect("subscription without name")
-                        .to_owned(),
-                    _operation_type: operations::OperationType::Subscription,
+                    name: operation_name(&s.name),
+                    anonymous: s.name.is_none(),
+                    operation_type: operations::OperationType::Subscription,
                     object_id: on,
                     selection_set: Vec::with_capacity(s.selection_set.items.len()),
+                    position: s.position,
                 };
 
                 resolved_query.operations.push(resolved_operation);
             }
             graphql_parser::query::Definition::Operation(
-                graphql_parser::query::OperationDefinition::SelectionSet(_),
+                graphql_parser::query::OperationDefinition::SelectionSet(selection_set),
             ) => {
-                return Err(QueryValidationError::new(
-                    crate::constants::SELECTION_SET_AT_ROOT.to_owned(),
-                ))
+                // The `{ ... }` shorthand is an anonymous query without variables.
+                resolved_query.operations.push(ResolvedOperation {
+                    name: anonymous_operation_name.to_owned(),
+                    anonymous: true,
+                    operation_type: operations::OperationType::Query,
+                    object_id: schema.query_type(),
+                    selection_set: Vec::with_capacity(selection_set.items.len()),
+                    position: selection_set.span.0,
+                });
             }
         }
     }
 
-    Ok(())
+    if resolved_query.operations.len() > 1 {
+        if let Some(operation) = resolved_query
+            .operations
+            .iter()
+            .find(|operation| operation.anonymous)
+        {
+            let position = operation.position;
+            resolved_query.errors.push(
+                QueryValidationError::new(
+                    "An anonymous operation must be the only operation in its document. Please give names to the operations.".to_owned(),
+                )
+                .at(position),
+            );
+        }
+    }
 }
 
 fn resolve_fragment(
     query: &mut Query,
     schema: &Schema,
     fragment_definition: &graphql_parser::query::FragmentDefinition,
-) -> Result<(), QueryValidationError> {
+) {
     let graphql_parser::query::TypeCondition::On(on) = &fragment_definition.type_condition;
-    let on = schema.find_type(&on).ok_or_else(|| {
-        QueryValidationError::new(format!(
-            "Could not find type `{}` referenced by fragment `{}`",
-            on, fragment_definition.name
-        ))
-    })?;
+
+    // Fragments on unknown types are reported in `create_roots`.
+    let on = match schema.find_type(&on) {
+        Some(on) => on,
+        None => return,
+    };
 
     let (id, _) = query
         .find_fragment(&fragment_definition.name)
-        .ok_or_else(|| {
-            QueryValidationError::new(format!(
-                "Could not find fragment `{}`.",
-                fragment_definition.name
-            ))
-        })?;
+        .expect("fragment created in create_roots");
 
     resolve_selection(
         query,
@@ -210,9 +347,7 @@ fn resolve_fragment(
         &fragment_definition.selection_set,
         SelectionParent::Fragment(id),
         schema,
-    )?;
-
-    Ok(())
+    );
 }
 
 fn resolve_union_selection(
@@ -221,42 +356,36 @@ fn resolve_union_selection(
     selection_set: &graphql_parser::query::SelectionSet,
     parent: SelectionParent,
     schema: &Schema,
-) -> Result<(), QueryValidationError> {
+) {
     for item in selection_set.items.iter() {
         match item {
             graphql_parser::query::Selection::Field(field) => {
                 if field.name == TYPENAME_FIELD {
-                    let id = query.push_selection(Selection::Typename, parent);
+                    let id = query.push_selection(Selection::Typename, parent, field.position);
+                    query.set_directives(id, &field.directives);
                     parent.add_to_selection_set(query, id);
                 } else {
-                    return Err(QueryValidationError::new(format!(
-                        "Invalid field selection on union field ({:?})",
-                        parent
-                    )));
+                    query.errors.push(
+                        QueryValidationError::new(format!(
+                            "Invalid field selection on union field ({:?})",
+                            parent
+                        ))
+                        .at(field.position),
+                    );
                 }
             }
             graphql_parser::query::Selection::InlineFragment(inline_fragment) => {
-                let selection_id = resolve_inline_fragment(query, schema, inline_fragment, parent)?;
-                parent.add_to_selection_set(query, selection_id);
+                if let Some(selection_id) =
+                    resolve_inline_fragment(query, schema, inline_fragment, parent)
+                {
+                    parent.add_to_selection_set(query, selection_id);
+                }
             }
             graphql_parser::query::Selection::FragmentSpread(fragment_spread) => {
-                let (fragment_id, _fragment) = query
-                    .find_fragment(&fragment_spread.fragment_name)
-                    .ok_or_else(|| {
-                        QueryValidationError::new(format!(
-                            "Could not find fragment `{}` referenced by fragment spread.",
-                            fragment_spread.fragment_name
-                        ))
-                    })?;
-
-                let id = query.push_selection(Selection::FragmentSpread(fragment_id), parent);
-
-                parent.add_to_selection_set(query, id);
+                resolve_fragment_spread(query, fragment_spread, parent)
             }
         }
     }
-
-    Ok(())
 }
 
 fn resolve_object_selection<'a>(
@@ -265,34 +394,43 @@ fn resolve_object_selection<'a>(
     selection_set: &graphql_parser::query::SelectionSet,
     parent: SelectionParent,
     schema: &'a Schema,
-) -> Result<(), QueryValidationError> {
+) {
     for item in selection_set.items.iter() {
         match item {
             graphql_parser::query::Selection::Field(field) => {
                 if field.name == TYPENAME_FIELD {
-                    let id = query.push_selection(Selection::Typename, parent);
+                    let id = query.push_selection(Selection::Typename, parent, field.position);
+                    query.set_directives(id, &field.directives);
                     parent.add_to_selection_set(query, id);
                     continue;
                 }
 
-                let (field_id, schema_field) = object
-                    .get_field_by_name(&field.name, schema)
-                    .ok_or_else(|| {
-                        QueryValidationError::new(format!(
-                            "No field named {} on {}",
-                            &field.name,
-                            object.name()
-                        ))
-                    })?;
+                let (field_id, schema_field) = match object.get_field_by_name(&field.name, schema) {
+                    Some(field) => field,
+                    None => {
+                        query.errors.push(
+                            QueryValidationError::new(format!(
+                                "No field named {} on {}",
+                                &field.name,
+                                object.name()
+                            ))
+                            .at(field.position),
+                        );
+                        continue;
+                    }
+                };
 
                 let id = query.push_selection(
                     Selection::Field(SelectedField {
                         alias: field.alias.clone(),
                         field_id,
+                        arguments: field.arguments.clone(),
                         selection_set: Vec::with_capacity(selection_set.items.len()),
                     }),
                     parent,
+                    field.position,
                 );
+                query.set_directives(id, &field.directives);
 
                 resolve_selection(
                     query,
@@ -300,33 +438,49 @@ fn resolve_object_selection<'a>(
                     &field.selection_set,
                     SelectionParent::Field(id),
                     schema,
-                )?;
+                );
 
                 parent.add_to_selection_set(query, id);
             }
             graphql_parser::query::Selection::InlineFragment(inline) => {
-                let selection_id = resolve_inline_fragment(query, schema, inline, parent)?;
-
-                parent.add_to_selection_set(query, selection_id);
+                if let Some(selection_id) = resolve_inline_fragment(query, schema, inline, parent) {
+                    parent.add_to_selection_set(query, selection_id);
+                }
             }
             graphql_parser::query::Selection::FragmentSpread(fragment_spread) => {
-                let (fragment_id, _fragment) = query
-                    .find_fragment(&fragment_spread.fragment_name)
-                    .ok_or_else(|| {
-                        QueryValidationError::new(format!(
-                            "Could not find fragment `{}` referenced by fragment spread.",
-                            fragment_spread.fragment_name
-                        ))
-                    })?;
-
-                let id = query.push_selection(Selection::FragmentSpread(fragment_id), parent);
-
-                parent.add_to_selection_set(query, id);
+                resolve_fragment_spread(query, fragment_spread, parent)
             }
         }
     }
+}
+
+fn resolve_fragment_spread(
+    query: &mut Query,
+    fragment_spread: &graphql_parser::query::FragmentSpread,
+    parent: SelectionParent,
+) {
+    let fragment_id = match query.find_fragment(&fragment_spread.fragment_name) {
+        Some((fragment_id, _fragment)) => fragment_id,
+        None => {
+            query.errors.push(
+                QueryValidationError::new(format!(
+                    "Could not find fragment `{}` referenced by fragment spread.",
+                    fragment_spread.fragment_name
+                ))
+                .at(fragment_spread.position),
+            );
+            return;
+        }
+    };
 
-    Ok(())
+    let id = query.push_selection(
+        Selection::FragmentSpread(fragment_id),
+        parent,
+        fragment_spread.position,
+    );
+    query.set_directives(id, &fragment_spread.directives);
+
+    parent.add_to_selection_set(query, id);
 }
 
 fn resolve_selection(
@@ -335,30 +489,31 @@ fn resolve_selection(
     selection_set: &graphql_parser::query::SelectionSet,
     parent: SelectionParent,
     schema: &Schema,
-) -> Result<(), QueryValidationError> {
+) {
     match on {
         TypeId::Object(oid) => {
             let object = schema.get_object(oid);
-            resolve_object_selection(ctx, object, selection_set, parent, schema)?;
+            resolve_object_selection(ctx, object, selection_set, parent, schema);
         }
         TypeId::Interface(interface_id) => {
             let interface = schema.get_interface(interface_id);
-            resolve_object_selection(ctx, interface, selection_set, parent, schema)?;
+            resolve_object_selection(ctx, interface, selection_set, parent, schema);
         }
         TypeId::Union(union_id) => {
-            resolve_union_selection(ctx, union_id, selection_set, parent, schema)?;
+            resolve_union_selection(ctx, union_id, selection_set, parent, schema);
         }
         other => {
             if !selection_set.items.is_empty() {
-                return Err(QueryValidationError::new(format!(
-                    "Selection set on non-object, non-interface type. ({:?})",
-                    other
-                )));
+                ctx.errors.push(
+                    QueryValidationError::new(format!(
+                        "Selection set on non-object, non-interface type. ({:?})",
+                        other
+                    ))
+                    .at(selection_set.span.0),
+                );
             }
         }
     };
-
-    Ok(())
 }
 
 fn resolve_inline_fragment(
@@ -366,17 +521,24 @@ fn resolve_inline_fragment(
     schema: &Schema,
     inline_fragment: &graphql_parser::query::InlineFragment,
     parent: SelectionParent,
-) -> Result<SelectionId, QueryValidationError> {
-    let graphql_parser::query::TypeCondition::On(on) = inline_fragment
-        .type_condition
-        .as_ref()
-        .expect("missing type condition on inline fragment");
-    let type_id = schema.find_type(on).ok_or_else(|| {
-        QueryValidationError::new(format!(
-            "Could not find type `{}` referenced by inline fragment.",
-            on
-        ))
-    })?;
+) -> Option<SelectionId> {
+    let type_id = match &inline_fragment.type_condition {
+        Some(graphql_parser::query::TypeCondition::On(on)) => match schema.find_type(on) {
+            Some(type_id) => type_id,
+            None => {
+                query.errors.push(
+                    QueryValidationError::new(format!(
+                        "Could not find type `{}` referenced by inline fragment.",
+                        on
+                    ))
+                    .at(inline_fragment.position),
+                );
+                return None;
+            }
+        },
+        // Without a type condition, the inline fragment is on the parent type.
+        None => parent.schema_type_id(&BoundQuery { query, schema }),
+    };
 
     let id = query.push_selection(
         Selection::InlineFragment(InlineFragment {
@@ -384,7 +546,9 @@ fn resolve_inline_fragment(
             selection_set: Vec::with_capacity(inline_fragment.selection_set.items.len()),
         }),
         parent,
+        inline_fragment.position,
     );
+    query.set_directives(id, &inline_fragment.directives);
 
     resolve_selection(
         query,
@@ -392,27 +556,28 @@ fn resolve_inline_fragment(
         &inline_fragment.selection_set,
         SelectionParent::InlineFragment(id),
         schema,
-    )?;
+    );
 
-    Ok(id)
+    Some(id)
 }
 
 fn resolve_operation(
     query: &mut Query,
     schema: &Schema,
     operation: &graphql_parser::query::OperationDefinition,
-) -> Result<(), QueryValidationError> {
+    anonymous_operation_name: &str,
+) {
+    // Operations on root types missing from the schema are reported in `create_roots`.
     match operation {
         graphql_parser::query::OperationDefinition::Mutation(m) => {
-            let on = schema.mutation_type().ok_or_else(|| {
-                QueryValidationError::new(
-                    "Query contains a mutation operation, but the schema has no mutation type."
-                        .to_owned(),
-                )
-            })?;
-            let on = schema.get_object(on);
+            let on = match schema.mutation_type() {
+                Some(on) => schema.get_object(on),
+                None => return,
+            };
 
-            let (id, _) = query.find_operation(m.name.as_ref().unwrap()).unwrap();
+            let (id, _) = query
+                .find_operation(m.name.as_deref().unwrap_or(anonymous_operation_name))
+                .unwrap();
 
             resolve_variables(query, &m.variable_definitions, schema, id);
             resolve_object_selection(
@@ -421,11 +586,13 @@ fn resolve_operation(
                 &m.selection_set,
                 SelectionParent::Operation(id),
                 schema,
-            )?;
+            );
         }
         graphql_parser::query::OperationDefinition::Query(q) => {
             let on = schema.get_object(schema.query_type());
-            let (id, _) = query.find_operation(q.name.as_ref().unwrap()).unwrap();
+            let (id, _) = query
+                .find_operation(q.name.as_deref().unwrap_or(anonymous_operation_name))
+                .unwrap();
 
             resolve_variables(query, &q.variable_definitions, schema, id);
             resolve_object_selection(
@@ -434,12 +601,16 @@ fn resolve_operation(
                 &q.selection_set,
                 SelectionParent::Operation(id),
                 schema,
-            )?;
+            );
         }
         graphql_parser::query::OperationDefinition::Subscription(s) => {
-            let on = schema.subscription_type().ok_or_else(|| QueryValidationError::new("Query contains a subscription operation, but the schema has no subscription type.".into()))?;
-            let on = schema.get_object(on);
-            let (id, _) = query.find_operation(s.name.as_ref().unwrap()).unwrap();
+            let on = match schema.subscription_type() {
+                Some(on) => schema.get_object(on),
+                None => return,
+            };
+            let (id, _) = query
+                .find_operation(s.name.as_deref().unwrap_or(anonymous_operation_name))
+                .unwrap();
 
             resolve_variables(query, &s.variable_definitions, schema, id);
             resolve_object_selection(
@@ -448,14 +619,21 @@ fn resolve_operation(
                 &s.selection_set,
                 SelectionParent::Operation(id),
                 schema,
-            )?;
+            );
         }
-        graphql_parser::query::OperationDefinition::SelectionSet(_) => {
-            unreachable!("unnamed queries are not supported")
+        graphql_parser::query::OperationDefinition::SelectionSet(selection_set) => {
+            let on = schema.get_object(schema.query_type());
+            let (id, _) = query.find_operation(anonymous_operation_name).unwrap();
+
+            resolve_object_selection(
+                query,
+                on,
+                selection_set,
+                SelectionParent::Operation(id),
+                schema,
+            );
         }
     }
-
-    Ok(())
 }
 
 #[derive(Default)]
@@ -464,19 +642,73 @@ pub(crate) struct Query {
     operations: Vec<ResolvedOperation>,
     selection_parent_idx: HashMap<SelectionId, SelectionParent>,
     selections: Vec<Selection>,
+    /// The position of each selection in the document, indexed like `selections`.
+    selection_positions: Vec<Pos>,
+    /// The directives on selections, for the selections that have any.
+    selection_directives: HashMap<SelectionId, Vec<graphql_parser::query::Directive>>,
     variables: Vec<ResolvedVariable>,
+    /// The errors found while resolving the document, taken by `resolve`.
+    errors: Vec<QueryValidationError>,
 }
 
 impl Query {
-    fn push_selection(&mut self, node: Selection, parent: SelectionParent) -> SelectionId {
+    fn push_selection(
+        &mut self,
+        node: Selection,
+        parent: SelectionParent,
+        position: Pos,
+    ) -> SelectionId {
         let id = SelectionId(self.selections.len() as u32);
         self.selections.push(node);
+        self.selection_positions.push(position);
 
         self.selection_parent_idx.insert(id, parent);
 
         id
     }
 
+    fn set_directives(
+        &mut self,
+        selection_id: SelectionId,
+        directives: &[graphql_parser::query::Directive],
+    ) {
+        if !directives.is_empty() {
+            self.selection_directives
+                .insert(selection_id, directives.to_vec());
+        }
+    }
+
+    pub(crate) fn selection_directives(
+        &self,
+        selection_id: SelectionId,
+    ) -> &[graphql_parser::query::Directive] {
+        self.selection_directives
+            .get(&selection_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Whether the selection may be absent from the response because of a `@skip` or `@include`
+    /// directive, on the selection itself or on the inline fragments it is in.
+    pub(crate) fn selection_is_conditional(&self, selection_id: SelectionId) -> bool {
+        let mut item = selection_id;
+
+        loop {
+            if self
+                .selection_directives(item)
+                .iter()
+                .any(|directive| directive.name == "skip" || directive.name == "include")
+            {
+                return true;
+            }
+
+            match self.selection_parent_idx.get(&item) {
+                Some(SelectionParent::InlineFragment(parent_id)) => item = *parent_id,
+                _ => return false,
+            }
+        }
+    }
+
     pub fn operations(&self) -> impl Iterator<Item = (OperationId, &ResolvedOperation)> {
         walk_operations(self)
     }
@@ -487,6 +719,10 @@ impl Query {
             .expect("Query.get_selection")
     }
 
+    pub(crate) fn selection_position(&self, id: SelectionId) -> Pos {
+        self.selection_positions[id.0 as usize]
+    }
+
     pub(crate) fn get_fragment(&self, id: ResolvedFragmentId) -> &ResolvedFragment {
         self.fragments
             .get(id.0 as usize)
@@ -547,6 +783,7 @@ pub(crate) struct ResolvedVariable {
     pub(crate) name: String,
     pub(crate) default: Option<graphql_parser::query::Value>,
     pub(crate) r#type: StoredFieldType,
+    pub(crate) position: Pos,
 }
 
 impl ResolvedVariable {
@@ -620,15 +857,59 @@ fn resolve_variables(
     operation_id: OperationId,
 ) {
     for var in variables {
+        let mut named_type = &var.var_type;
+
+        while let graphql_parser::query::Type::ListType(inner)
+        | graphql_parser::query::Type::NonNullType(inner) = named_type
+        {
+            named_type = inner;
+        }
+
+        if let graphql_parser::query::Type::NamedType(name) = named_type {
+            if schema.find_type(name).is_none() {
+                query.errors.push(
+                    QueryValidationError::new(format!(
+                        "Could not find type `{}` for variable `${}` in schema.",
+                        name, var.name
+                    ))
+                    .at(var.position),
+                );
+                continue;
+            }
+        }
+
         query.variables.push(ResolvedVariable {
             operation_id,
             name: var.name.clone(),
             default: var.default_value.clone(),
             r#type: resolve_field_type(schema, &var.var_type),
+            position: var.position,
         });
     }
 }
 
+/// Check that the operations use no deprecated fields or arguments, for the deny deprecation
+/// strategy.
+pub(crate) fn validate_deprecations(
+    operation_ids: impl Iterator<Item = OperationId>,
+    query: &BoundQuery<'_>,
+) -> Result<(), QueryValidationErrors> {
+    let mut errors = Vec::new();
+
+    for operation_id in operation_ids {
+        validation::validate_deprecations(operation_id, query, &mut errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(QueryValidationErrors {
+            errors,
+            document: None,
+        })
+    }
+}
+
 pub(crate) fn walk_operations(
     query: &Query,
 ) -> impl Iterator<Item = (OperationId, &ResolvedOperation)> {
@@ -673,6 +954,38 @@ pub(crate) fn all_used_types(operation_id: OperationId, query: &BoundQuery<'_>)
     used_types
 }
 
+/// The fields the operation selects, directly or through fragments.
+pub(crate) fn walk_operation_fields(
+    operation_id: OperationId,
+    query: &Query,
+) -> Vec<(SelectionId, &SelectedField)> {
+    let mut fields = Vec::new();
+    let mut visited_fragments = HashSet::new();
+    let mut selection_ids = query.get_operation(operation_id).selection_set.clone();
+
+    while let Some(selection_id) = selection_ids.pop() {
+        match query.get_selection(selection_id) {
+            Selection::Field(field) => {
+                fields.push((selection_id, field));
+                selection_ids.extend(&field.selection_set);
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                selection_ids.extend(&inline_fragment.selection_set)
+            }
+            Selection::FragmentSpread(fragment_id) => {
+                if visited_fragments.insert(*fragment_id) {
+                    selection_ids.extend(&query.get_fragment(*fragment_id).selection_set);
+                }
+            }
+            Selection::Typename => (),
+        }
+    }
+
+    fields.sort_by_key(|(selection_id, _)| selection_id.0);
+
+    fields
+}
+
 pub(crate) fn full_path_prefix(selection_id: SelectionId, query: &BoundQuery<'_>) -> String {
     let mut path = match query.query.get_selection(selection_id) {
         Selection::FragmentSpread(_) | Selection::InlineFragment(_) => Vec::new(),
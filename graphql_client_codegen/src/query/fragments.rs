@@ -1,5 +1,9 @@
-use super::{Query, ResolvedFragmentId, SelectionId};
+use super::{
+    merge_inline_fragments, BoundQuery, Query, ResolvedFragmentId, SelectedField, Selection,
+    SelectionId,
+};
 use crate::schema::TypeId;
+use graphql_parser::Pos;
 use heck::*;
 
 #[derive(Debug)]
@@ -7,18 +11,120 @@ pub(crate) struct ResolvedFragment {
     pub(crate) name: String,
     pub(crate) on: TypeId,
     pub(crate) selection_set: Vec<SelectionId>,
+    pub(crate) position: Pos,
 }
 
 impl ResolvedFragment {
     pub(super) fn to_path_segment(&self) -> String {
         self.name.to_camel_case()
     }
+
+    /// Whether a spread of this fragment in a selection on `parent_type` is generated as a
+    /// flattened field of the parent struct, rather than as a variant of its `on` enum. This is
+    /// the case for spreads on the parent type itself, and for all spreads on object types.
+    pub(crate) fn is_flattened_into(&self, parent_type: TypeId) -> bool {
+        self.on == parent_type || parent_type.as_object_id().is_some()
+    }
 }
 
+/// Whether the fragment spreads itself, directly or through other fragments. Its struct then has
+/// to be boxed where it is used.
 pub(crate) fn fragment_is_recursive(fragment_id: ResolvedFragmentId, query: &Query) -> bool {
-    let fragment = query.get_fragment(fragment_id);
+    let mut visited = Vec::new();
+    let mut to_visit = vec![fragment_id];
+
+    while let Some(id) = to_visit.pop() {
+        let mut spreads = Vec::new();
+        collect_spreads(
+            &query.get_fragment(id).selection_set,
+            query,
+            true,
+            &mut spreads,
+        );
+
+        for spread in spreads {
+            if spread == fragment_id {
+                return true;
+            }
+
+            if !visited.contains(&spread) {
+                visited.push(spread);
+                to_visit.push(spread);
+            }
+        }
+    }
+
+    false
+}
+
+/// The fragments spread in a selection set, including in its inline fragments, and in the
+/// subselections of its fields if `through_fields` is true.
+pub(super) fn collect_spreads(
+    selection_set: &[SelectionId],
+    query: &Query,
+    through_fields: bool,
+    spreads: &mut Vec<ResolvedFragmentId>,
+) {
+    for (_id, selection) in query.walk_selection_set(selection_set) {
+        match selection {
+            Selection::FragmentSpread(fragment_id) => spreads.push(*fragment_id),
+            Selection::InlineFragment(inline_fragment) => collect_spreads(
+                &inline_fragment.selection_set,
+                query,
+                through_fields,
+                spreads,
+            ),
+            Selection::Field(field) if through_fields => {
+                collect_spreads(&field.selection_set, query, through_fields, spreads)
+            }
+            Selection::Field(_) | Selection::Typename => (),
+        }
+    }
+}
+
+/// The field with this response key in the fragment, selected directly or through the fragments
+/// it flattens. These are all deserialized as part of the fragment struct.
+pub(crate) fn fragment_field_by_response_key<'a>(
+    fragment_id: ResolvedFragmentId,
+    response_key: &str,
+    query: &BoundQuery<'a>,
+) -> Option<&'a SelectedField> {
+    let mut visited = Vec::new();
+    find_fragment_field(fragment_id, response_key, query, &mut visited)
+}
+
+fn find_fragment_field<'a>(
+    fragment_id: ResolvedFragmentId,
+    response_key: &str,
+    query: &BoundQuery<'a>,
+    visited: &mut Vec<ResolvedFragmentId>,
+) -> Option<&'a SelectedField> {
+    if visited.contains(&fragment_id) {
+        return None;
+    }
+    visited.push(fragment_id);
+
+    let fragment = query.query.get_fragment(fragment_id);
+    let selection_set = merge_inline_fragments(&fragment.selection_set, fragment.on, query.query);
+
+    for id in &selection_set {
+        match query.query.get_selection(*id) {
+            Selection::Field(field) if field.response_key(query.schema) == response_key => {
+                return Some(field)
+            }
+            Selection::FragmentSpread(spread_id)
+                if query
+                    .query
+                    .get_fragment(*spread_id)
+                    .is_flattened_into(fragment.on) =>
+            {
+                if let Some(field) = find_fragment_field(*spread_id, response_key, query, visited) {
+                    return Some(field);
+                }
+            }
+            _ => (),
+        }
+    }
 
-    query
-        .walk_selection_set(&fragment.selection_set)
-        .any(|(_id, selection)| selection.contains_fragment(fragment_id, query))
+    None
 }
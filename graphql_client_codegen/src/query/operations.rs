@@ -1,5 +1,6 @@
 use super::SelectionId;
 use crate::schema::ObjectId;
+use graphql_parser::Pos;
 use heck::*;
 
 #[derive(Debug, Clone)]
@@ -11,9 +12,13 @@ pub(crate) enum OperationType {
 
 pub(crate) struct ResolvedOperation {
     pub(crate) name: String,
-    pub(crate) _operation_type: OperationType,
+    /// The operation has no name in the document. `name` is then the name of the struct under
+    /// derive, or of the query file.
+    pub(crate) anonymous: bool,
+    pub(crate) operation_type: OperationType,
     pub(crate) selection_set: Vec<SelectionId>,
     pub(crate) object_id: ObjectId,
+    pub(crate) position: Pos,
 }
 
 impl ResolvedOperation {
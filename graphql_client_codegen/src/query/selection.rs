@@ -1,22 +1,32 @@
 use super::{
-    BoundQuery, OperationId, Query, QueryValidationError, ResolvedFragmentId, SelectionId,
-    UsedTypes,
+    validation::selection_path, BoundQuery, OperationId, Query, QueryValidationError,
+    ResolvedFragmentId, SelectionId, UsedTypes,
 };
 use crate::schema::{Schema, StoredField, StoredFieldId, TypeId};
 use heck::CamelCase;
 
 /// This checks that the `on` clause on fragment spreads and inline fragments
-/// are valid in their context.
+/// are valid in their context: the type condition must have at least one possible type in common
+/// with the parent type (Possible Fragment Spreads).
 pub(super) fn validate_type_conditions(
     selection_id: SelectionId,
     query: &BoundQuery<'_>,
-) -> Result<(), QueryValidationError> {
+    errors: &mut Vec<QueryValidationError>,
+) {
     let selection = query.query.get_selection(selection_id);
 
-    let selected_type = match selection {
-        Selection::FragmentSpread(fragment_id) => query.query.get_fragment(*fragment_id).on,
-        Selection::InlineFragment(inline_fragment) => inline_fragment.type_id,
-        _ => return Ok(()),
+    let (selected_type, spread) = match selection {
+        Selection::FragmentSpread(fragment_id) => {
+            let fragment = query.query.get_fragment(*fragment_id);
+            (
+                fragment.on,
+                format!("fragment spread `...{}`", fragment.name),
+            )
+        }
+        Selection::InlineFragment(inline_fragment) => {
+            (inline_fragment.type_id, "inline fragment".to_owned())
+        }
+        _ => return,
     };
 
     let parent_schema_type_id = query
@@ -27,43 +37,40 @@ pub(super) fn validate_type_conditions(
         .schema_type_id(query);
 
     if parent_schema_type_id == selected_type {
-        return Ok(());
+        return;
     }
 
-    match parent_schema_type_id {
-        TypeId::Union(union_id) => {
-            let union = query.schema.get_union(union_id);
+    let position = query.query.selection_position(selection_id);
 
-            if !union
-                .variants
-                .iter()
-                .any(|variant| *variant == selected_type)
-            {
-                return Err(QueryValidationError::new(format!(
-                    "The spread {}... on {} is not valid.",
-                    union.name,
-                    selected_type.name(query.schema)
-                )));
-            }
-        }
-        TypeId::Interface(interface_id) => {
-            let mut variants = query
-                .schema
-                .objects()
-                .filter(|(_, obj)| obj.implements_interfaces.contains(&interface_id));
-
-            if !variants.any(|(id, _)| TypeId::Object(id) == selected_type) {
-                return Err(QueryValidationError::new(format!(
-                    "The spread {}... on {} is not valid.",
-                    parent_schema_type_id.name(query.schema),
-                    selected_type.name(query.schema),
-                )));
-            }
-        }
-        _ => (),
+    let parent_possible_types = query.schema.possible_types(parent_schema_type_id);
+
+    if !query
+        .schema
+        .possible_types(selected_type)
+        .iter()
+        .any(|type_id| parent_possible_types.contains(type_id))
+    {
+        errors.push(QueryValidationError::new(format!(
+            "The {spread} on `{selected}` at `{path}` can never apply: `{parent}` and `{selected}` have no possible type in common.",
+            spread = spread,
+            selected = selected_type.name(query.schema),
+            path = selection_path(selection_id, query),
+            parent = parent_schema_type_id.name(query.schema),
+        )).at(position));
+        return;
     }
 
-    Ok(())
+    let is_abstract = |type_id: TypeId| matches!(type_id, TypeId::Interface(_) | TypeId::Union(_));
+
+    if is_abstract(parent_schema_type_id) && is_abstract(selected_type) {
+        errors.push(QueryValidationError::new(format!(
+            "The {spread} on `{selected}` at `{path}` is in a selection on `{parent}`. graphql-client cannot generate code for it. Please spread it on the object types of `{parent}` instead.",
+            spread = spread,
+            selected = selected_type.name(query.schema),
+            path = selection_path(selection_id, query),
+            parent = parent_schema_type_id.name(query.schema),
+        )).at(position));
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -76,7 +83,7 @@ pub(super) enum SelectionParent {
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
 impl SelectionParent {
-    fn schema_type_id(&self, query: &BoundQuery<'_>) -> TypeId {
+    pub(super) fn schema_type_id(&self, query: &BoundQuery<'_>) -> TypeId {
         match self {
             SelectionParent::Fragment(fragment_id) => query.query.get_fragment(*fragment_id).on,
             SelectionParent::Operation(operation_id) => {
@@ -202,17 +209,6 @@ impl Selection {
         }
     }
 
-    pub(crate) fn contains_fragment(&self, fragment_id: ResolvedFragmentId, query: &Query) -> bool {
-        match self {
-            Selection::FragmentSpread(id) => *id == fragment_id,
-            _ => self.subselection().iter().any(|selection_id| {
-                query
-                    .get_selection(*selection_id)
-                    .contains_fragment(fragment_id, query)
-            }),
-        }
-    }
-
     pub(crate) fn subselection(&self) -> &[SelectionId] {
         match self {
             Selection::Field(field) => field.selection_set.as_slice(),
@@ -221,7 +217,7 @@ impl Selection {
         }
     }
 
-    pub(super) fn to_path_segment(&self, query: &BoundQuery<'_>) -> String {
+    pub(crate) fn to_path_segment(&self, query: &BoundQuery<'_>) -> String {
         match self {
             Selection::Field(field) => field
                 .alias
@@ -245,10 +241,48 @@ pub(crate) struct InlineFragment {
     pub(crate) selection_set: Vec<SelectionId>,
 }
 
+impl InlineFragment {
+    /// Whether the selections of this inline fragment, in a selection on `parent_type`, are
+    /// generated as part of the parent struct rather than in one of the variants of its `on`
+    /// enum. This is the case for inline fragments without a type condition or on the parent type
+    /// itself, and for all inline fragments on object types.
+    pub(crate) fn is_merged_into(&self, parent_type: TypeId) -> bool {
+        self.type_id == parent_type || parent_type.as_object_id().is_some()
+    }
+}
+
+/// The selection set, where the inline fragments that are merged into the parent struct are
+/// replaced with their own selections, recursively.
+pub(crate) fn merge_inline_fragments(
+    selection_set: &[SelectionId],
+    parent_type: TypeId,
+    query: &Query,
+) -> Vec<SelectionId> {
+    let mut merged = Vec::with_capacity(selection_set.len());
+
+    for id in selection_set {
+        match query.get_selection(*id) {
+            Selection::InlineFragment(inline_fragment)
+                if inline_fragment.is_merged_into(parent_type) =>
+            {
+                merged.extend(merge_inline_fragments(
+                    &inline_fragment.selection_set,
+                    parent_type,
+                    query,
+                ))
+            }
+            _ => merged.push(*id),
+        }
+    }
+
+    merged
+}
+
 #[derive(Debug)]
 pub(crate) struct SelectedField {
     pub(crate) alias: Option<String>,
     pub(crate) field_id: StoredFieldId,
+    pub(crate) arguments: Vec<(String, graphql_parser::query::Value)>,
     pub(crate) selection_set: Vec<SelectionId>,
 }
 
@@ -257,6 +291,12 @@ impl SelectedField {
         self.alias.as_deref()
     }
 
+    /// The key of the field in the response: its alias, or its name.
+    pub(crate) fn response_key<'a>(&'a self, schema: &'a Schema) -> &'a str {
+        self.alias()
+            .unwrap_or_else(|| self.schema_field(schema).name.as_str())
+    }
+
     pub(crate) fn schema_field<'a>(&self, schema: &'a Schema) -> &'a StoredField {
         schema.get_field(self.field_id)
     }
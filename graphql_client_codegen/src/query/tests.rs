@@ -0,0 +1,393 @@
+use crate::schema::Schema;
+
+const SCHEMA: &str = r#"
+schema {
+    query: Query
+}
+
+enum Episode {
+    NEWHOPE
+    EMPIRE
+    JEDI
+}
+
+input ReviewFilter {
+    minStars: Int!
+    episodes: [Episode!]
+    limit: Int! = 10
+}
+
+type Character {
+    name: String!
+    friends(first: Int = 10, after: String): [Character!]!
+}
+
+interface Node {
+    id: ID!
+}
+
+type Droid implements Node {
+    id: ID!
+    primaryFunction: String
+}
+
+type Starship implements Node {
+    id: ID!
+    length: Float
+}
+
+union SearchResult = Droid | Starship
+
+type Query {
+    hero(episode: Episode): Character
+    character(id: ID!): Character
+    reviews(filter: ReviewFilter, stars: [Int!]): [String!]!
+    node(id: ID!): Node
+    search(text: String!): [SearchResult!]!
+}
+"#;
+
+fn resolve(query: &str) -> Result<super::Query, super::QueryValidationErrors> {
+    let schema = Schema::from(graphql_parser::parse_schema(SCHEMA).unwrap());
+    let query = graphql_parser::parse_query(query).unwrap();
+
+    super::resolve(&schema, &query, "Anonymous")
+}
+
+fn validation_error(query: &str) -> String {
+    match resolve(query) {
+        Ok(_) => panic!("The query should not be valid:\n{}", query),
+        Err(err) => err
+            .errors
+            .iter()
+            .map(|error| error.message.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+#[test]
+fn valid_arguments_are_accepted() {
+    resolve(
+        r#"
+        query Valid($episode: Episode!, $id: ID!, $stars: [Int!]) {
+            hero(episode: $episode) { name friends(first: 3) { name } }
+            character(id: 12) { friends { name } }
+            other: character(id: $id) { name }
+            reviews(filter: { minStars: 3, episodes: [JEDI, EMPIRE] }, stars: 5)
+            all: reviews(stars: $stars)
+        }
+        "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn unknown_arguments_are_rejected() {
+    assert_eq!(
+        validation_error(r#"query Q { hero(season: JEDI) { name } }"#),
+        "Unknown argument `season` at `Q.hero`: `Query.hero` has no such argument."
+    );
+}
+
+#[test]
+fn missing_required_arguments_are_rejected() {
+    assert_eq!(
+        validation_error(r#"query Q { hero { friends { name } } character { name } }"#),
+        "Missing required argument `id` of type `ID!` at `Q.character`."
+    );
+}
+
+#[test]
+fn mistyped_literals_are_rejected() {
+    assert_eq!(
+        validation_error(r#"query Q { hero { friends(first: "ten") { name } } }"#),
+        "Invalid value `\"ten\"` for argument `first` at `Q.hero.friends`: expected a value of type `Int`."
+    );
+    assert_eq!(
+        validation_error(r#"query Q { hero(episode: PHANTOM) { name } }"#),
+        "Invalid value `PHANTOM` for argument `episode` at `Q.hero`: expected a value of type `Episode`."
+    );
+    assert_eq!(
+        validation_error(r#"query Q { character(id: null) { name } }"#),
+        "Invalid value `null` for argument `id` at `Q.character`: expected a value of type `ID!`."
+    );
+    assert!(
+        validation_error(r#"query Q { reviews(filter: { minStars: 3, stars: 4 }) }"#)
+            .starts_with("Invalid value")
+    );
+}
+
+#[test]
+fn input_literals_without_a_required_field_are_rejected() {
+    assert_eq!(
+        validation_error(r#"query Q { reviews(filter: { episodes: [JEDI] }) }"#),
+        "Invalid value `{episodes: [JEDI]}` for argument `filter` at `Q.reviews`: expected a value of type `ReviewFilter`."
+    );
+    // Fields with a default value can be left out.
+    assert!(resolve(r#"query Q { reviews(filter: { minStars: 3 }) }"#).is_ok());
+}
+
+#[test]
+fn variables_of_another_type_are_rejected() {
+    assert_eq!(
+        validation_error(
+            r#"
+            query Q($episode: String) { ...HeroFields }
+            fragment HeroFields on Query { villain: hero(episode: $episode) { name } }
+            "#
+        ),
+        "Variable `$episode` of type `String` is passed to argument `episode` at `HeroFields.villain` in operation `Q`, which expects `Episode`."
+    );
+    assert!(validation_error(
+        r#"query Q($episodes: Episode) { reviews(filter: { minStars: 1, episodes: $episodes }) }"#
+    )
+    .starts_with("Variable `$episodes` of type `Episode`"));
+}
+
+#[test]
+fn undefined_variables_are_rejected() {
+    assert_eq!(
+        validation_error(
+            r#"
+            query Q { ...HeroFields }
+            fragment HeroFields on Query { hero(episode: $episode) { name } }
+            "#
+        ),
+        "Variable `$episode` is used at `HeroFields.hero` in operation `Q`, but it is not defined by the operation."
+    );
+}
+
+#[test]
+fn unused_variables_are_rejected() {
+    assert_eq!(
+        validation_error(
+            r#"query Q($id: ID!, $episode: Episode) { hero(episode: $episode) { name } }"#
+        ),
+        "Variable `$id` is defined by operation `Q`, but it is never used."
+    );
+}
+
+#[test]
+fn variables_in_directives_are_validated() {
+    resolve(
+        r#"
+        query Q($withFriends: Boolean!, $skipName: Boolean = false) {
+            hero {
+                name @skip(if: $skipName)
+                friends @include(if: $withFriends) { name }
+            }
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validation_error(
+            r#"query Q($withFriends: Boolean) { hero { friends @include(if: $withFriends) { name } } }"#
+        ),
+        "Variable `$withFriends` of type `Boolean` is passed to argument `if` of directive `@include` at `Q.hero.friends` in operation `Q`, which expects `Boolean!`."
+    );
+}
+
+#[test]
+fn nullable_variables_are_only_allowed_in_non_null_positions_with_a_default() {
+    assert_eq!(
+        validation_error(r#"query Q($id: ID) { character(id: $id) { name } }"#),
+        "Variable `$id` of type `ID` is passed to argument `id` at `Q.character` in operation `Q`, which expects `ID!`."
+    );
+    assert!(validation_error(
+        r#"query Q($minStars: Int) { reviews(filter: { minStars: $minStars }) }"#
+    )
+    .starts_with("Variable `$minStars` of type `Int` is passed to argument `filter`"));
+
+    resolve(r#"query Q($id: ID = 1000) { character(id: $id) { name } }"#).unwrap();
+    resolve(r#"query Q($stars: [Int!]!) { reviews(stars: $stars) }"#).unwrap();
+    assert!(
+        validation_error(r#"query Q($stars: [Int]) { reviews(stars: $stars) }"#)
+            .ends_with("which expects `[Int!]`.")
+    );
+}
+
+#[test]
+fn repeated_fields_are_accepted() {
+    resolve(
+        r#"
+        query Q {
+            hero { name friends { name } }
+            hero { friends { name } ...HeroName }
+            reviews(stars: 5)
+            reviews(stars: 5)
+        }
+        fragment HeroName on Character { name }
+        "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn conflicting_response_keys_are_rejected() {
+    assert_eq!(
+        validation_error(r#"query Q { hero { name: friends { name } name } }"#),
+        "The response key `name` at `Q.hero.name` is used for both `friends` and `name`. Use different aliases for different fields."
+    );
+    assert_eq!(
+        validation_error(
+            r#"
+            query Q { hero(episode: JEDI) { name } ...Hero }
+            fragment Hero on Query { hero(episode: EMPIRE) { name } }
+            "#
+        ),
+        "The response key `hero` at `Q.hero` is used for `hero` with different arguments. Use different aliases, or the same arguments."
+    );
+    assert_eq!(
+        validation_error(
+            r#"query Q { hero { friends { name } } hero { friends { name: friends { name } } } }"#
+        ),
+        "The response key `name` at `Q.hero.friends.name` is used for both `name` and `friends`. Use different aliases for different fields."
+    );
+}
+
+#[test]
+fn fields_selected_directly_and_through_a_fragment_are_accepted() {
+    assert!(resolve(
+        r#"
+        query Q { hero { friends { name friends { name } } ...HeroFriends } }
+        fragment HeroFriends on Character { friends { name } }
+        "#
+    )
+    .is_ok());
+}
+
+#[test]
+fn fragment_cycles_are_rejected() {
+    assert_eq!(
+        validation_error(
+            r#"
+            query Q { ...A }
+            fragment A on Query { hero { name } ...B }
+            fragment B on Query { ... on Query { ...A } }
+            "#
+        ),
+        "Fragment `A` spreads itself (A -> B -> A) without selecting a field in between, so its selection would be infinite."
+    );
+
+    resolve(
+        r#"
+        query Q { hero { ...A } }
+        fragment A on Character { name friends { ...B } }
+        fragment B on Character { ...A }
+        "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn unused_fragments_are_rejected() {
+    assert_eq!(
+        validation_error(
+            r#"
+            query Q { hero { name } }
+            fragment HeroName on Character { name }
+            "#
+        ),
+        "Fragment `HeroName` is defined but never used."
+    );
+}
+
+#[test]
+fn impossible_fragment_spreads_are_rejected() {
+    assert_eq!(
+        validation_error(
+            r#"
+            query Q { hero { ...DroidFields } }
+            fragment DroidFields on Droid { primaryFunction }
+            "#
+        ),
+        "The fragment spread `...DroidFields` on `Droid` at `Q.hero` can never apply: `Character` and `Droid` have no possible type in common."
+    );
+    assert_eq!(
+        validation_error(
+            r#"query Q { node(id: 1) { __typename ... on Character { name } } }"#
+        ),
+        "The inline fragment on `Character` at `Q.node` can never apply: `Node` and `Character` have no possible type in common."
+    );
+
+    resolve(
+        r#"
+        query Q {
+            node(id: 1) { __typename id ... on Droid { primaryFunction } }
+            search(text: "x") { __typename ... on Starship { length } }
+        }
+        "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn anonymous_operations_are_named() {
+    let query = resolve(r#"{ hero { name } }"#).unwrap();
+    let (_id, operation) = query.operations().next().unwrap();
+
+    assert_eq!(operation.name, "Anonymous");
+    assert!(operation.anonymous);
+
+    resolve(r#"query ($id: ID!) { character(id: $id) { name } }"#).unwrap();
+}
+
+#[test]
+fn anonymous_operations_must_be_alone() {
+    assert_eq!(
+        validation_error(r#"{ hero { name } } query Q { hero { name } }"#),
+        "An anonymous operation must be the only operation in its document. Please give names to the operations."
+    );
+}
+
+#[test]
+fn all_errors_are_reported_with_their_position() {
+    let query = r#"query Q($unused: Int) {
+    hero(season: JEDI) { name }
+    character { name }
+}
+"#;
+
+    assert_eq!(
+        resolve(query).err().unwrap().to_string(),
+        "line 2, column 5: Unknown argument `season` at `Q.hero`: `Query.hero` has no such argument.\n\
+         line 3, column 5: Missing required argument `id` of type `ID!` at `Q.character`.\n\
+         line 1, column 9: Variable `$unused` is defined by operation `Q`, but it is never used."
+    );
+
+    assert_eq!(
+        resolve("query Q {\n  hero { nickname }\n  villain { name }\n}")
+            .err()
+            .unwrap()
+            .to_string(),
+        "line 2, column 10: No field named nickname on Character\n\
+         line 3, column 3: No field named villain on Query"
+    );
+}
+
+#[test]
+fn errors_are_rendered_as_snippets_of_the_document() {
+    let query = "query Q {\n  hero { nickname }\n\t...Missing\n}\n";
+    let errors = resolve(query)
+        .err()
+        .unwrap()
+        .in_document(std::path::Path::new("queries/hero.graphql"), query);
+
+    assert_eq!(
+        errors.to_string(),
+        "error: No field named nickname on Character\n \
+         --> queries/hero.graphql:2:10\n  \
+         |\n\
+         2 |   hero { nickname }\n  \
+         |          ^^^^^^^^\n\
+         \n\
+         error: Could not find fragment `Missing` referenced by fragment spread.\n \
+         --> queries/hero.graphql:3:12\n  \
+         |\n\
+         3 | \t...Missing\n  \
+         | \t   ^^^^^^^"
+    );
+}
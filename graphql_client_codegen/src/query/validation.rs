@@ -1,9 +1,20 @@
-use super::{full_path_prefix, BoundQuery, Query, QueryValidationError, Selection, SelectionId};
-use crate::schema::TypeId;
+use super::{
+    fragments::collect_spreads, full_path_prefix, walk_operation_fields, walk_operation_variables,
+    BoundQuery, OperationId, Query, QueryValidationError, ResolvedFragmentId, ResolvedVariable,
+    SelectedField, Selection, SelectionId, SelectionParent,
+};
+use crate::schema::{
+    collect_deprecated_literals, graphql_type_string, Schema, StoredInputFieldType, TypeId,
+    DEFAULT_SCALARS,
+};
+use crate::type_qualifiers::GraphqlTypeQualifier;
+use graphql_parser::query::Value;
+use std::collections::HashSet;
 
 pub(super) fn validate_typename_presence(
     query: &BoundQuery<'_>,
-) -> Result<(), QueryValidationError> {
+    errors: &mut Vec<QueryValidationError>,
+) {
     for fragment in query.query.fragments.iter() {
         let type_id = match fragment.on {
             id @ TypeId::Interface(_) | id @ TypeId::Union(_) => id,
@@ -11,11 +22,11 @@ pub(super) fn validate_typename_presence(
         };
 
         if !selection_set_contains_type_name(fragment.on, &fragment.selection_set, query.query) {
-            return Err(QueryValidationError::new(format!(
+            errors.push(QueryValidationError::new(format!(
                 "The `{}` fragment uses `{}` but does not select `__typename` on it. graphql-client cannot generate code for it. Please add `__typename` to the selection.",
                 &fragment.name,
                 type_id.name(query.schema),
-            )));
+            )).at(fragment.position));
         }
     }
 
@@ -35,15 +46,13 @@ pub(super) fn validate_typename_presence(
 
     for selection in union_and_interface_field_selections {
         if !selection_set_contains_type_name(selection.1, selection.2, query.query) {
-            return Err(QueryValidationError::new(format!(
+            errors.push(QueryValidationError::new(format!(
                 "The query uses `{path}` at `{selected_type}` but does not select `__typename` on it. graphql-client cannot generate code for it. Please add `__typename` to the selection.",
                 path = full_path_prefix(selection.0, query),
                 selected_type = selection.1.name(query.schema)
-            )));
+            )).at(query.query.selection_position(selection.0)));
         }
     }
-
-    Ok(())
 }
 
 fn selection_set_contains_type_name(
@@ -64,9 +73,959 @@ fn selection_set_contains_type_name(
                     return true;
                 }
             }
+            Selection::InlineFragment(inline_fragment) => {
+                if inline_fragment.type_id == parent_type_id
+                    && selection_set_contains_type_name(
+                        parent_type_id,
+                        &inline_fragment.selection_set,
+                        query,
+                    )
+                {
+                    return true;
+                }
+            }
             _ => (),
         }
     }
 
     false
 }
+
+/// Checks the arguments of every field selection: they must be defined on the field, required
+/// arguments must be provided, and literal values must match the type of the argument.
+pub(super) fn validate_arguments(query: &BoundQuery<'_>, errors: &mut Vec<QueryValidationError>) {
+    for (selection_id, selection) in query.query.selections() {
+        let field = match selection {
+            Selection::Field(field) => field,
+            _ => continue,
+        };
+        let schema_field = field.schema_field(query.schema);
+        let position = query.query.selection_position(selection_id);
+
+        for (name, value) in &field.arguments {
+            let argument = match schema_field.get_argument(name) {
+                Some(argument) => argument,
+                None => {
+                    errors.push(
+                        QueryValidationError::new(format!(
+                            "Unknown argument `{argument}` at `{path}`: `{field}` has no such argument.",
+                            argument = name,
+                            path = selection_path(selection_id, query),
+                            field = schema_field.qualified_name(query.schema),
+                        ))
+                        .at(position),
+                    );
+                    continue;
+                }
+            };
+
+            if !literal_matches_type(
+                value,
+                argument.r#type.id,
+                &argument.r#type.qualifiers,
+                query.schema,
+            ) {
+                errors.push(QueryValidationError::new(format!(
+                    "Invalid value `{value}` for argument `{argument}` at `{path}`: expected a value of type `{expected}`.",
+                    value = value,
+                    argument = name,
+                    path = selection_path(selection_id, query),
+                    expected = graphql_type_string(
+                        argument.r#type.id,
+                        &argument.r#type.qualifiers,
+                        query.schema
+                    ),
+                )).at(position));
+            }
+        }
+
+        for argument in schema_field
+            .arguments
+            .iter()
+            .filter(|argument| argument.is_required())
+        {
+            if !field
+                .arguments
+                .iter()
+                .any(|(name, _)| *name == argument.name)
+            {
+                errors.push(
+                    QueryValidationError::new(format!(
+                    "Missing required argument `{argument}` of type `{argument_type}` at `{path}`.",
+                    argument = argument.name,
+                    argument_type = graphql_type_string(
+                        argument.r#type.id,
+                        &argument.r#type.qualifiers,
+                        query.schema
+                    ),
+                    path = selection_path(selection_id, query),
+                ))
+                    .at(position),
+                );
+            }
+        }
+    }
+}
+
+/// The All Variables Defined, All Variables Used and Variables In Allowed Position rules: every
+/// variable used in an operation (directly or in the fragments it spreads) must be defined by the
+/// operation, with a compatible type, and every variable defined by the operation must be used.
+pub(super) fn validate_variables(query: &BoundQuery<'_>, errors: &mut Vec<QueryValidationError>) {
+    for (operation_id, operation) in query.query.operations() {
+        let usages = operation_variable_usages(operation_id, query);
+
+        for usage in &usages {
+            let position = query.query.selection_position(usage.selection_id);
+            let variable = match walk_operation_variables(operation_id, query.query)
+                .map(|(_id, variable)| variable)
+                .find(|variable| variable.name == usage.name)
+            {
+                Some(variable) => variable,
+                None => {
+                    errors.push(QueryValidationError::new(format!(
+                        "Variable `${variable}` is used at `{path}` in operation `{operation}`, but it is not defined by the operation.",
+                        variable = usage.name,
+                        path = selection_path(usage.selection_id, query),
+                        operation = operation.name,
+                    )).at(position));
+                    continue;
+                }
+            };
+
+            let location_type = match &usage.r#type {
+                Some(location_type) => location_type,
+                None => continue,
+            };
+
+            if !variable_usage_is_allowed(variable, usage, location_type) {
+                errors.push(QueryValidationError::new(format!(
+                    "Variable `${variable}` of type `{variable_type}` is passed to {argument} at `{path}` in operation `{operation}`, which expects `{expected}`.",
+                    variable = usage.name,
+                    variable_type = graphql_type_string(
+                        variable.r#type.id,
+                        &variable.r#type.qualifiers,
+                        query.schema
+                    ),
+                    argument = usage.describe_argument(),
+                    path = selection_path(usage.selection_id, query),
+                    operation = operation.name,
+                    expected = graphql_type_string(
+                        location_type.id,
+                        &location_type.qualifiers,
+                        query.schema
+                    ),
+                )).at(position));
+            }
+        }
+
+        for (_id, variable) in walk_operation_variables(operation_id, query.query) {
+            if !usages.iter().any(|usage| usage.name == variable.name) {
+                errors.push(QueryValidationError::new(format!(
+                    "Variable `${variable}` is defined by operation `{operation}`, but it is never used.",
+                    variable = variable.name,
+                    operation = operation.name,
+                )).at(variable.position));
+            }
+        }
+    }
+}
+
+fn variable_usage_is_allowed(
+    variable: &ResolvedVariable,
+    usage: &VariableUsage<'_>,
+    location_type: &StoredInputFieldType,
+) -> bool {
+    let variable_is_required = variable
+        .r#type
+        .qualifiers
+        .first()
+        .map(GraphqlTypeQualifier::is_required)
+        .unwrap_or(false);
+
+    match location_type.qualifiers.split_first() {
+        // A nullable variable can be passed to a non-null location if a default value will be
+        // used in place of null.
+        Some((GraphqlTypeQualifier::Required, nullable_location)) if !variable_is_required => {
+            let has_non_null_default = match &variable.default {
+                Some(Value::Null) | None => false,
+                Some(_) => true,
+            };
+
+            (has_non_null_default || usage.location_has_default)
+                && types_are_compatible(
+                    &variable.r#type.qualifiers,
+                    nullable_location,
+                    variable.r#type.id == location_type.id,
+                )
+        }
+        _ => types_are_compatible(
+            &variable.r#type.qualifiers,
+            &location_type.qualifiers,
+            variable.r#type.id == location_type.id,
+        ),
+    }
+}
+
+/// The AreTypesCompatible algorithm from the spec, on qualifiers. `same_named_type` tells whether
+/// the innermost types are the same.
+fn types_are_compatible(
+    variable: &[GraphqlTypeQualifier],
+    location: &[GraphqlTypeQualifier],
+    same_named_type: bool,
+) -> bool {
+    match (variable.split_first(), location.split_first()) {
+        (
+            Some((GraphqlTypeQualifier::Required, variable)),
+            Some((GraphqlTypeQualifier::Required, location)),
+        ) => types_are_compatible(variable, location, same_named_type),
+        (_, Some((GraphqlTypeQualifier::Required, _))) => false,
+        (Some((GraphqlTypeQualifier::Required, variable)), _) => {
+            types_are_compatible(variable, location, same_named_type)
+        }
+        (
+            Some((GraphqlTypeQualifier::List, variable)),
+            Some((GraphqlTypeQualifier::List, location)),
+        ) => types_are_compatible(variable, location, same_named_type),
+        (None, None) => same_named_type,
+        _ => false,
+    }
+}
+
+/// Whether a literal value can be coerced to the given input type. Variables are accepted here,
+/// their type is checked against the position they are used in separately.
+fn literal_matches_type(
+    value: &Value,
+    type_id: TypeId,
+    qualifiers: &[GraphqlTypeQualifier],
+    schema: &Schema,
+) -> bool {
+    let (is_required, qualifiers) = match qualifiers.split_first() {
+        Some((GraphqlTypeQualifier::Required, rest)) => (true, rest),
+        _ => (false, qualifiers),
+    };
+
+    match (value, qualifiers.split_first()) {
+        (Value::Variable(_), _) => true,
+        (Value::Null, _) => !is_required,
+        (Value::List(items), Some((GraphqlTypeQualifier::List, inner))) => items
+            .iter()
+            .all(|item| literal_matches_type(item, type_id, inner, schema)),
+        // A single value is coerced to a list with one element.
+        (value, Some((GraphqlTypeQualifier::List, inner))) => {
+            literal_matches_type(value, type_id, inner, schema)
+        }
+        (value, _) => named_type_accepts(value, type_id, schema),
+    }
+}
+
+fn named_type_accepts(value: &Value, type_id: TypeId, schema: &Schema) -> bool {
+    match type_id {
+        TypeId::Scalar(scalar_id) => match (schema.get_scalar(scalar_id).name.as_str(), value) {
+            ("Int", Value::Int(_))
+            | ("Float", Value::Int(_))
+            | ("Float", Value::Float(_))
+            | ("String", Value::String(_))
+            | ("Boolean", Value::Boolean(_))
+            | ("ID", Value::Int(_))
+            | ("ID", Value::String(_)) => true,
+            // We know nothing about the literal format of custom scalars.
+            (name, _) => !DEFAULT_SCALARS.contains(&name),
+        },
+        TypeId::Enum(enum_id) => match value {
+            Value::Enum(variant) => schema.get_enum(enum_id).variants.contains(variant),
+            _ => false,
+        },
+        TypeId::Input(input_id) => match value {
+            Value::Object(fields) => {
+                let input = schema.get_input(input_id);
+
+                input
+                    .required_fields()
+                    .all(|name| fields.contains_key(name))
+                    && fields.iter().all(|(name, value)| {
+                        input
+                            .fields
+                            .iter()
+                            .find(|(field_name, _)| field_name == name)
+                            .map(|(_, field_type)| {
+                                literal_matches_type(
+                                    value,
+                                    field_type.id,
+                                    &field_type.qualifiers,
+                                    schema,
+                                )
+                            })
+                            .unwrap_or(false)
+                    })
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// A variable, used in a field or directive argument.
+pub(super) struct VariableUsage<'a> {
+    pub(super) name: &'a str,
+    /// The selection the argument belongs to.
+    pub(super) selection_id: SelectionId,
+    pub(super) argument_name: &'a str,
+    /// The directive the argument belongs to, if it is not a field argument.
+    pub(super) directive_name: Option<&'a str>,
+    /// The type expected where the variable is used: the type of the argument, or the type of a
+    /// list item or an input field when the variable is nested in the argument value. It is
+    /// unknown for the arguments of custom directives.
+    pub(super) r#type: Option<StoredInputFieldType>,
+    /// Whether the variable is the whole argument value, and the argument has a default value.
+    pub(super) location_has_default: bool,
+}
+
+impl VariableUsage<'_> {
+    fn describe_argument(&self) -> String {
+        match self.directive_name {
+            Some(directive_name) => format!(
+                "argument `{}` of directive `@{}`",
+                self.argument_name, directive_name
+            ),
+            None => format!("argument `{}`", self.argument_name),
+        }
+    }
+}
+
+/// All the variables used in an operation, including in the fragments it spreads.
+pub(super) fn operation_variable_usages<'a>(
+    operation_id: OperationId,
+    query: &BoundQuery<'a>,
+) -> Vec<VariableUsage<'a>> {
+    let mut usages = Vec::new();
+    let mut visited_fragments = HashSet::new();
+
+    collect_selection_set_usages(
+        &query.query.get_operation(operation_id).selection_set,
+        query,
+        &mut visited_fragments,
+        &mut usages,
+    );
+
+    usages
+}
+
+fn collect_selection_set_usages<'a>(
+    selection_set: &'a [SelectionId],
+    query: &BoundQuery<'a>,
+    visited_fragments: &mut HashSet<ResolvedFragmentId>,
+    usages: &mut Vec<VariableUsage<'a>>,
+) {
+    for (selection_id, selection) in query.query.walk_selection_set(selection_set) {
+        collect_directive_usages(selection_id, query, usages);
+
+        match selection {
+            Selection::Field(field) => {
+                let schema_field = field.schema_field(query.schema);
+
+                for (name, value) in &field.arguments {
+                    if let Some(argument) = schema_field.get_argument(name) {
+                        collect_value_usages(
+                            value,
+                            &ValueLocation {
+                                selection_id,
+                                argument_name: name,
+                                directive_name: None,
+                                r#type: Some((argument.r#type.id, &argument.r#type.qualifiers)),
+                                has_default: argument.has_default_value,
+                            },
+                            query.schema,
+                            usages,
+                        );
+                    }
+                }
+
+                collect_selection_set_usages(
+                    &field.selection_set,
+                    query,
+                    visited_fragments,
+                    usages,
+                );
+            }
+            Selection::InlineFragment(inline_fragment) => collect_selection_set_usages(
+                &inline_fragment.selection_set,
+                query,
+                visited_fragments,
+                usages,
+            ),
+            Selection::FragmentSpread(fragment_id) => {
+                if visited_fragments.insert(*fragment_id) {
+                    collect_selection_set_usages(
+                        &query.query.get_fragment(*fragment_id).selection_set,
+                        query,
+                        visited_fragments,
+                        usages,
+                    );
+                }
+            }
+            Selection::Typename => (),
+        }
+    }
+}
+
+/// The `if` argument of `@skip` and `@include` is a `Boolean!`. The arguments of other directives
+/// are not known.
+fn collect_directive_usages<'a>(
+    selection_id: SelectionId,
+    query: &BoundQuery<'a>,
+    usages: &mut Vec<VariableUsage<'a>>,
+) {
+    const REQUIRED: &[GraphqlTypeQualifier] = &[GraphqlTypeQualifier::Required];
+
+    let boolean = query.schema.find_type("Boolean");
+
+    for directive in query.query.selection_directives(selection_id) {
+        for (name, value) in &directive.arguments {
+            let r#type = match (directive.name.as_str(), name.as_str(), boolean) {
+                ("skip", "if", Some(boolean)) | ("include", "if", Some(boolean)) => {
+                    Some((boolean, REQUIRED))
+                }
+                _ => None,
+            };
+
+            collect_value_usages(
+                value,
+                &ValueLocation {
+                    selection_id,
+                    argument_name: name,
+                    directive_name: Some(&directive.name),
+                    r#type,
+                    has_default: false,
+                },
+                query.schema,
+                usages,
+            );
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ValueLocation<'a, 'q> {
+    selection_id: SelectionId,
+    argument_name: &'a str,
+    directive_name: Option<&'a str>,
+    /// The expected type, if known.
+    r#type: Option<(TypeId, &'q [GraphqlTypeQualifier])>,
+    has_default: bool,
+}
+
+fn collect_value_usages<'a>(
+    value: &'a Value,
+    location: &ValueLocation<'a, '_>,
+    schema: &Schema,
+    usages: &mut Vec<VariableUsage<'a>>,
+) {
+    match value {
+        Value::Variable(name) => usages.push(VariableUsage {
+            name,
+            selection_id: location.selection_id,
+            argument_name: location.argument_name,
+            directive_name: location.directive_name,
+            r#type: location
+                .r#type
+                .map(|(id, qualifiers)| StoredInputFieldType {
+                    id,
+                    qualifiers: qualifiers.to_vec(),
+                }),
+            location_has_default: location.has_default,
+        }),
+        Value::List(items) => {
+            let item_type = location.r#type.and_then(|(id, qualifiers)| {
+                let qualifiers = match qualifiers.split_first() {
+                    Some((GraphqlTypeQualifier::Required, rest)) => rest,
+                    _ => qualifiers,
+                };
+
+                match qualifiers.split_first() {
+                    Some((GraphqlTypeQualifier::List, inner)) => Some((id, inner)),
+                    _ => None,
+                }
+            });
+
+            for item in items {
+                collect_value_usages(
+                    item,
+                    &ValueLocation {
+                        r#type: item_type,
+                        has_default: false,
+                        ..*location
+                    },
+                    schema,
+                    usages,
+                );
+            }
+        }
+        Value::Object(fields) => {
+            let input = match location.r#type {
+                Some((TypeId::Input(input_id), _)) => Some(schema.get_input(input_id)),
+                _ => None,
+            };
+
+            for (name, value) in fields {
+                let field_type = input
+                    .and_then(|input| {
+                        input
+                            .fields
+                            .iter()
+                            .find(|(field_name, _)| field_name == name)
+                    })
+                    .map(|(_, field_type)| (field_type.id, field_type.qualifiers.as_slice()));
+
+                collect_value_usages(
+                    value,
+                    &ValueLocation {
+                        r#type: field_type,
+                        has_default: false,
+                        ..*location
+                    },
+                    schema,
+                    usages,
+                );
+            }
+        }
+        _ => (),
+    }
+}
+
+/// With the deny deprecation strategy, the deprecated fields, arguments, enum values and input
+/// fields the operation uses, directly or through fragments, are errors.
+pub(super) fn validate_deprecations(
+    operation_id: OperationId,
+    query: &BoundQuery<'_>,
+    errors: &mut Vec<QueryValidationError>,
+) {
+    let reason = |deprecation: Option<&str>| deprecation.unwrap_or("no reason given").to_owned();
+
+    for (selection_id, field) in walk_operation_fields(operation_id, query.query) {
+        let schema_field = field.schema_field(query.schema);
+        let position = query.query.selection_position(selection_id);
+
+        if let Some(deprecation) = schema_field.deprecation() {
+            errors.push(
+                QueryValidationError::new(format!(
+                    "The deprecated field `{field}` is selected at `{path}`: {reason}",
+                    field = schema_field.qualified_name(query.schema),
+                    path = selection_path(selection_id, query),
+                    reason = reason(deprecation),
+                ))
+                .at(position),
+            );
+        }
+
+        for (name, value) in &field.arguments {
+            let argument = match schema_field.get_argument(name) {
+                Some(argument) => argument,
+                None => continue,
+            };
+
+            if let Some(deprecation) = argument.deprecation() {
+                errors.push(
+                    QueryValidationError::new(format!(
+                        "The deprecated argument `{argument}` of `{field}` is used at `{path}`: {reason}",
+                        argument = name,
+                        field = schema_field.qualified_name(query.schema),
+                        path = selection_path(selection_id, query),
+                        reason = reason(deprecation),
+                    ))
+                    .at(position),
+                );
+            }
+
+            let mut literals = Vec::new();
+            collect_deprecated_literals(value, argument.r#type.id, query.schema, &mut literals);
+
+            for literal in literals {
+                errors.push(
+                    QueryValidationError::new(format!(
+                        "The deprecated {item} is used at `{path}`: {reason}",
+                        item = literal.item,
+                        path = selection_path(selection_id, query),
+                        reason = reason(literal.reason),
+                    ))
+                    .at(position),
+                );
+            }
+        }
+    }
+
+    for (_id, variable) in walk_operation_variables(operation_id, query.query) {
+        let default = match &variable.default {
+            Some(default) => default,
+            None => continue,
+        };
+        let mut literals = Vec::new();
+        collect_deprecated_literals(default, variable.r#type.id, query.schema, &mut literals);
+
+        for literal in literals {
+            errors.push(
+                QueryValidationError::new(format!(
+                    "The deprecated {item} is used in the default value of `${variable}` in `{operation}`: {reason}",
+                    item = literal.item,
+                    variable = variable.name,
+                    operation = query.query.get_operation(operation_id).name,
+                    reason = reason(literal.reason),
+                ))
+                .at(variable.position),
+            );
+        }
+    }
+}
+
+/// A fragment must not spread itself without selecting a field in between: its selection would be
+/// infinite. Fragments that spread themselves inside a field selection are fine, their structs are
+/// boxed. Each cycle is reported once, on its first fragment.
+pub(super) fn validate_fragment_cycles(
+    query: &BoundQuery<'_>,
+    errors: &mut Vec<QueryValidationError>,
+) {
+    let mut reported = HashSet::new();
+
+    for idx in 0..query.query.fragments.len() {
+        let fragment_id = ResolvedFragmentId(idx as u32);
+
+        if reported.contains(&fragment_id) {
+            continue;
+        }
+
+        let mut path = vec![fragment_id];
+
+        if find_fragment_cycle(&mut path, query.query) {
+            let cycle: Vec<&str> = path
+                .iter()
+                .map(|id| query.query.get_fragment(*id).name.as_str())
+                .collect();
+
+            errors.push(QueryValidationError::new(format!(
+                "Fragment `{name}` spreads itself ({cycle}) without selecting a field in between, so its selection would be infinite.",
+                name = cycle[0],
+                cycle = cycle.join(" -> "),
+            )).at(query.query.get_fragment(fragment_id).position));
+
+            reported.extend(path);
+        }
+    }
+}
+
+/// Every fragment must be spread somewhere in the document.
+pub(super) fn validate_unused_fragments(
+    query: &BoundQuery<'_>,
+    errors: &mut Vec<QueryValidationError>,
+) {
+    let mut used_fragments = Vec::new();
+
+    for (_id, selection) in query.query.selections() {
+        if let Selection::FragmentSpread(fragment_id) = selection {
+            used_fragments.push(*fragment_id);
+        }
+    }
+
+    for (idx, fragment) in query.query.fragments.iter().enumerate() {
+        if !used_fragments.contains(&ResolvedFragmentId(idx as u32)) {
+            errors.push(
+                QueryValidationError::new(format!(
+                    "Fragment `{}` is defined but never used.",
+                    fragment.name
+                ))
+                .at(fragment.position),
+            );
+        }
+    }
+}
+
+/// Extend `path` until it loops back to its first fragment, through spreads at the same level.
+fn find_fragment_cycle(path: &mut Vec<ResolvedFragmentId>, query: &Query) -> bool {
+    let current = *path.last().expect("non-empty fragment path");
+    let mut spreads = Vec::new();
+    collect_spreads(
+        &query.get_fragment(current).selection_set,
+        query,
+        false,
+        &mut spreads,
+    );
+
+    for spread in spreads {
+        if spread == path[0] {
+            path.push(spread);
+            return true;
+        }
+
+        if path.contains(&spread) {
+            // A cycle that does not go through the first fragment, it is reported for another one.
+            continue;
+        }
+
+        path.push(spread);
+        if find_fragment_cycle(path, query) {
+            return true;
+        }
+        path.pop();
+    }
+
+    false
+}
+
+/// The Overlapping Fields Can Be Merged rule: fields with the same response key in a selection
+/// set, including the fields selected through fragments, must be the same field with the same
+/// arguments (unless they are on different object types) and have compatible types.
+///
+/// A field that is selected both directly and through a flattened fragment spread is only
+/// generated once, in the fragment struct, so the direct selection must not select more than the
+/// fragment.
+pub(super) fn validate_overlapping_fields(
+    query: &BoundQuery<'_>,
+    errors: &mut Vec<QueryValidationError>,
+) {
+    let mut compared = HashSet::new();
+
+    let selection_sets = query
+        .query
+        .operations()
+        .map(|(_id, operation)| {
+            (
+                operation.selection_set.as_slice(),
+                TypeId::Object(operation.object_id),
+            )
+        })
+        .chain(
+            query
+                .query
+                .fragments
+                .iter()
+                .map(|fragment| (fragment.selection_set.as_slice(), fragment.on)),
+        )
+        .chain(
+            query
+                .query
+                .selections()
+                .filter_map(|(_id, selection)| match selection {
+                    Selection::Field(field) => Some((
+                        field.selection_set.as_slice(),
+                        field.schema_field(query.schema).r#type.id,
+                    )),
+                    Selection::InlineFragment(inline_fragment) => Some((
+                        inline_fragment.selection_set.as_slice(),
+                        inline_fragment.type_id,
+                    )),
+                    _ => None,
+                }),
+        );
+
+    for (selection_set, parent_type) in selection_sets {
+        let mut fields = Vec::new();
+        collect_fields(
+            selection_set,
+            parent_type,
+            query,
+            &mut HashSet::new(),
+            &mut fields,
+        );
+        check_overlapping_fields(&fields, false, query, &mut compared, errors);
+    }
+}
+
+/// A field in a selection set, possibly selected through fragments.
+#[derive(Clone, Copy)]
+struct FieldInSet<'a> {
+    selection_id: SelectionId,
+    field: &'a SelectedField,
+    parent_type: TypeId,
+    response_key: &'a str,
+}
+
+fn collect_fields<'a>(
+    selection_set: &'a [SelectionId],
+    parent_type: TypeId,
+    query: &BoundQuery<'a>,
+    visited_fragments: &mut HashSet<ResolvedFragmentId>,
+    fields: &mut Vec<FieldInSet<'a>>,
+) {
+    for (selection_id, selection) in query.query.walk_selection_set(selection_set) {
+        match selection {
+            Selection::Field(field) => fields.push(FieldInSet {
+                selection_id,
+                field,
+                parent_type,
+                response_key: field.response_key(query.schema),
+            }),
+            Selection::InlineFragment(inline_fragment) => collect_fields(
+                &inline_fragment.selection_set,
+                inline_fragment.type_id,
+                query,
+                visited_fragments,
+                fields,
+            ),
+            Selection::FragmentSpread(fragment_id) => {
+                if visited_fragments.insert(*fragment_id) {
+                    let fragment = query.query.get_fragment(*fragment_id);
+                    collect_fields(
+                        &fragment.selection_set,
+                        fragment.on,
+                        query,
+                        visited_fragments,
+                        fields,
+                    );
+                }
+            }
+            Selection::Typename => (),
+        }
+    }
+}
+
+fn check_overlapping_fields(
+    fields: &[FieldInSet<'_>],
+    parents_are_mutually_exclusive: bool,
+    query: &BoundQuery<'_>,
+    compared: &mut HashSet<(SelectionId, SelectionId)>,
+    errors: &mut Vec<QueryValidationError>,
+) {
+    for (idx, a) in fields.iter().enumerate() {
+        for b in fields[idx + 1..]
+            .iter()
+            .filter(|b| b.response_key == a.response_key)
+        {
+            if let Err(error) = check_field_pair(
+                a,
+                b,
+                parents_are_mutually_exclusive,
+                query,
+                compared,
+                errors,
+            ) {
+                errors.push(error.at(query.query.selection_position(b.selection_id)));
+            }
+        }
+    }
+}
+
+fn check_field_pair(
+    a: &FieldInSet<'_>,
+    b: &FieldInSet<'_>,
+    parents_are_mutually_exclusive: bool,
+    query: &BoundQuery<'_>,
+    compared: &mut HashSet<(SelectionId, SelectionId)>,
+    errors: &mut Vec<QueryValidationError>,
+) -> Result<(), QueryValidationError> {
+    if a.selection_id == b.selection_id || !compared.insert((a.selection_id, b.selection_id)) {
+        return Ok(());
+    }
+
+    let schema_field_a = a.field.schema_field(query.schema);
+    let schema_field_b = b.field.schema_field(query.schema);
+    let path = || selection_path(a.selection_id, query);
+
+    let mutually_exclusive = parents_are_mutually_exclusive
+        || (a.parent_type != b.parent_type
+            && a.parent_type.as_object_id().is_some()
+            && b.parent_type.as_object_id().is_some());
+
+    if !mutually_exclusive {
+        if schema_field_a.name != schema_field_b.name {
+            return Err(QueryValidationError::new(format!(
+                "The response key `{key}` at `{path}` is used for both `{a}` and `{b}`. Use different aliases for different fields.",
+                key = a.response_key,
+                path = path(),
+                a = schema_field_a.name,
+                b = schema_field_b.name,
+            )));
+        }
+
+        if !same_arguments(a.field, b.field) {
+            return Err(QueryValidationError::new(format!(
+                "The response key `{key}` at `{path}` is used for `{field}` with different arguments. Use different aliases, or the same arguments.",
+                key = a.response_key,
+                path = path(),
+                field = schema_field_a.name,
+            )));
+        }
+    }
+
+    let (type_a, type_b) = (&schema_field_a.r#type, &schema_field_b.r#type);
+    let is_leaf = |type_id: TypeId| matches!(type_id, TypeId::Scalar(_) | TypeId::Enum(_));
+
+    let same_shape = type_a.qualifiers == type_b.qualifiers
+        && if is_leaf(type_a.id) || is_leaf(type_b.id) {
+            type_a.id == type_b.id
+        } else {
+            true
+        };
+
+    if !same_shape {
+        return Err(QueryValidationError::new(format!(
+            "The response key `{key}` at `{path}` is used for fields with incompatible types `{a}` and `{b}`.",
+            key = a.response_key,
+            path = path(),
+            a = graphql_type_string(type_a.id, &type_a.qualifiers, query.schema),
+            b = graphql_type_string(type_b.id, &type_b.qualifiers, query.schema),
+        )));
+    }
+
+    // The subselections are merged in the response, so they must be mergeable too.
+    let mut subfields = Vec::new();
+    collect_fields(
+        &a.field.selection_set,
+        type_a.id,
+        query,
+        &mut HashSet::new(),
+        &mut subfields,
+    );
+    collect_fields(
+        &b.field.selection_set,
+        type_b.id,
+        query,
+        &mut HashSet::new(),
+        &mut subfields,
+    );
+
+    check_overlapping_fields(&subfields, mutually_exclusive, query, compared, errors);
+
+    Ok(())
+}
+
+fn same_arguments(a: &SelectedField, b: &SelectedField) -> bool {
+    a.arguments.len() == b.arguments.len()
+        && a.arguments.iter().all(|(name, value)| {
+            b.arguments
+                .iter()
+                .any(|(other_name, other_value)| name == other_name && value == other_value)
+        })
+}
+
+/// The path to a selection, from the operation or fragment it belongs to, using response keys,
+/// e.g. `HeroQuery.hero.friends`.
+pub(super) fn selection_path(selection_id: SelectionId, query: &BoundQuery<'_>) -> String {
+    let mut segments = Vec::new();
+    let mut item = selection_id;
+
+    loop {
+        if let Selection::Field(field) = query.query.get_selection(item) {
+            segments.push(
+                field
+                    .alias()
+                    .unwrap_or_else(|| field.schema_field(query.schema).name.as_str()),
+            );
+        }
+
+        match query.query.selection_parent_idx.get(&item) {
+            Some(SelectionParent::Field(id)) | Some(SelectionParent::InlineFragment(id)) => {
+                item = *id
+            }
+            Some(SelectionParent::Operation(id)) => {
+                segments.push(&query.query.get_operation(*id).name);
+                break;
+            }
+            Some(SelectionParent::Fragment(id)) => {
+                segments.push(&query.query.get_fragment(*id).name);
+                break;
+            }
+            None => break,
+        }
+    }
+
+    segments.reverse();
+    segments.join(".")
+}
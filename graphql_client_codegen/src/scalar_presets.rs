@@ -0,0 +1,65 @@
+//! Rust types for common custom scalars, opted into with `scalar_presets(...)` in the graphql
+//! attribute, or `--scalar-preset` in the CLI.
+//!
+//! The types are referred to through `graphql_client`, which re-exports the crates that define
+//! them when its `scalars-*` cargo features are enabled.
+
+/// A set of mappings from common custom scalar names to the types of a crate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalarPreset {
+    /// `DateTime` and `Date` are `chrono::DateTime<chrono::Utc>` and `chrono::NaiveDate`.
+    Chrono,
+    /// `UUID` is `uuid::Uuid`.
+    Uuid,
+    /// `URL` is `url::Url`.
+    Url,
+    /// `JSON` is `serde_json::Value`.
+    Json,
+    /// `Decimal` is `rust_decimal::Decimal`.
+    Decimal,
+}
+
+impl ScalarPreset {
+    /// The names of the presets, as accepted by `from_str`.
+    pub const NAMES: &'static [&'static str] = &["chrono", "uuid", "url", "json", "decimal"];
+
+    /// The Rust type for the custom scalar `graphql_name`, if the preset covers it.
+    pub(crate) fn scalar_type(self, graphql_name: &str) -> Option<&'static str> {
+        match (self, graphql_name) {
+            (ScalarPreset::Chrono, "DateTime") => Some(
+                "::graphql_client::__scalar_presets::chrono::DateTime<::graphql_client::__scalar_presets::chrono::Utc>",
+            ),
+            (ScalarPreset::Chrono, "Date") => {
+                Some("::graphql_client::__scalar_presets::chrono::NaiveDate")
+            }
+            (ScalarPreset::Uuid, "UUID") => Some("::graphql_client::__scalar_presets::uuid::Uuid"),
+            (ScalarPreset::Url, "URL") => Some("::graphql_client::__scalar_presets::url::Url"),
+            (ScalarPreset::Json, "JSON") => {
+                Some("::graphql_client::__scalar_presets::serde_json::Value")
+            }
+            (ScalarPreset::Decimal, "Decimal") => {
+                Some("::graphql_client::__scalar_presets::rust_decimal::Decimal")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for ScalarPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim() {
+            "chrono" => Ok(ScalarPreset::Chrono),
+            "uuid" => Ok(ScalarPreset::Uuid),
+            "url" => Ok(ScalarPreset::Url),
+            "json" => Ok(ScalarPreset::Json),
+            "decimal" => Ok(ScalarPreset::Decimal),
+            other => Err(format!(
+                "Unknown scalar preset `{}`. The presets are: {}",
+                other,
+                ScalarPreset::NAMES.join(", ")
+            )),
+        }
+    }
+}
@@ -19,6 +19,7 @@ struct StoredObjectField {
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct StoredObject {
     pub(crate) name: String,
+    pub(crate) description: Option<String>,
     pub(crate) fields: Vec<StoredFieldId>,
     pub(crate) implements_interfaces: Vec<InterfaceId>,
 }
@@ -30,12 +31,49 @@ pub(crate) struct StoredField {
     pub(crate) parent: StoredFieldParent,
     /// `Some(None)` should be interpreted as "deprecated, without reason"
     pub(crate) deprecation: Option<Option<String>>,
+    pub(crate) arguments: Vec<StoredFieldArgument>,
+    pub(crate) description: Option<String>,
 }
 
 impl StoredField {
     pub(crate) fn deprecation(&self) -> Option<Option<&str>> {
         self.deprecation.as_ref().map(|inner| inner.as_deref())
     }
+
+    pub(crate) fn get_argument(&self, name: &str) -> Option<&StoredFieldArgument> {
+        self.arguments.iter().find(|argument| argument.name == name)
+    }
+
+    /// The name of the field, qualified with the name of the type it is defined on, e.g.
+    /// `Query.hero`.
+    pub(crate) fn qualified_name(&self, schema: &Schema) -> String {
+        let parent_name = match self.parent {
+            StoredFieldParent::Object(id) => schema.get_object(id).name.as_str(),
+            StoredFieldParent::Interface(id) => schema.get_interface(id).name.as_str(),
+        };
+
+        format!("{}.{}", parent_name, self.name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct StoredFieldArgument {
+    pub(crate) name: String,
+    pub(crate) r#type: StoredInputFieldType,
+    pub(crate) has_default_value: bool,
+    /// `Some(None)` should be interpreted as "deprecated, without reason"
+    pub(crate) deprecation: Option<Option<String>>,
+}
+
+impl StoredFieldArgument {
+    pub(crate) fn deprecation(&self) -> Option<Option<&str>> {
+        self.deprecation.as_ref().map(|inner| inner.as_deref())
+    }
+
+    /// An argument is required if it is non-null and has no default value.
+    pub(crate) fn is_required(&self) -> bool {
+        !self.r#type.is_optional() && !self.has_default_value
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -74,6 +112,7 @@ struct InputFieldId(usize);
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct StoredInterface {
     name: String,
+    description: Option<String>,
     fields: Vec<StoredFieldId>,
 }
 
@@ -87,15 +126,32 @@ pub(crate) struct StoredFieldType {
     pub(crate) qualifiers: Vec<GraphqlTypeQualifier>,
 }
 
+/// Render a type the way it is written in GraphQL documents, e.g. `[Int!]!`.
+pub(crate) fn graphql_type_string(
+    id: TypeId,
+    qualifiers: &[GraphqlTypeQualifier],
+    schema: &Schema,
+) -> String {
+    qualifiers.iter().rev().fold(
+        id.name(schema).to_owned(),
+        |inner, qualifier| match qualifier {
+            GraphqlTypeQualifier::Required => format!("{}!", inner),
+            GraphqlTypeQualifier::List => format!("[{}]", inner),
+        },
+    )
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct StoredUnion {
     pub(crate) name: String,
+    pub(crate) description: Option<String>,
     pub(crate) variants: Vec<TypeId>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct StoredScalar {
     pub(crate) name: String,
+    pub(crate) description: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
@@ -136,7 +192,7 @@ impl TypeId {
         }
     }
 
-    fn as_object_id(&self) -> Option<ObjectId> {
+    pub(crate) fn as_object_id(&self) -> Option<ObjectId> {
         match self {
             TypeId::Object(id) => Some(*id),
             _ => None,
@@ -174,12 +230,46 @@ impl TypeId {
             TypeId::Input(s) => schema.get_input(*s).name.as_str(),
         }
     }
+
+    /// The description of the type in the schema, if any.
+    pub(crate) fn description<'a>(&self, schema: &'a Schema) -> Option<&'a str> {
+        match self {
+            TypeId::Object(obj) => schema.get_object(*obj).description.as_deref(),
+            TypeId::Scalar(s) => schema.get_scalar(*s).description.as_deref(),
+            TypeId::Interface(s) => schema.get_interface(*s).description.as_deref(),
+            TypeId::Union(s) => schema.get_union(*s).description.as_deref(),
+            TypeId::Enum(s) => schema.get_enum(*s).description.as_deref(),
+            TypeId::Input(s) => schema.get_input(*s).description.as_deref(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct StoredEnum {
     pub(crate) name: String,
     pub(crate) variants: Vec<String>,
+    /// The deprecated values, with the reason of the deprecation if any.
+    pub(crate) deprecated_variants: Vec<(String, Option<String>)>,
+    pub(crate) description: Option<String>,
+    /// The descriptions of the values that have one.
+    pub(crate) variant_descriptions: Vec<(String, String)>,
+}
+
+impl StoredEnum {
+    /// `Some(None)` should be interpreted as "deprecated, without reason"
+    pub(crate) fn variant_deprecation(&self, variant: &str) -> Option<Option<&str>> {
+        self.deprecated_variants
+            .iter()
+            .find(|(name, _)| name == variant)
+            .map(|(_, reason)| reason.as_deref())
+    }
+
+    pub(crate) fn variant_description(&self, variant: &str) -> Option<&str> {
+        self.variant_descriptions
+            .iter()
+            .find(|(name, _)| name == variant)
+            .map(|(_, description)| description.as_str())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -210,6 +300,13 @@ impl StoredInputFieldType {
 pub(crate) struct StoredInputType {
     pub(crate) name: String,
     pub(crate) fields: Vec<(String, StoredInputFieldType)>,
+    /// The deprecated fields, with the reason of the deprecation if any.
+    pub(crate) deprecated_fields: Vec<(String, Option<String>)>,
+    /// The fields that have a default value.
+    pub(crate) defaulted_fields: Vec<String>,
+    pub(crate) description: Option<String>,
+    /// The descriptions of the fields that have one.
+    pub(crate) field_descriptions: Vec<(String, String)>,
 }
 
 /// Intermediate representation for a parsed GraphQL schema used during code generation.
@@ -254,6 +351,7 @@ impl Schema {
         for scalar in DEFAULT_SCALARS {
             let id = self.push_scalar(StoredScalar {
                 name: (*scalar).to_owned(),
+                description: None,
             });
 
             self.names.insert((*scalar).to_owned(), TypeId::Scalar(id));
@@ -359,6 +457,19 @@ impl Schema {
             .map(|(idx, obj)| (ObjectId(idx as u32), obj))
     }
 
+    /// The object types a value of this type can have at runtime.
+    pub(crate) fn possible_types(&self, type_id: TypeId) -> Vec<TypeId> {
+        match type_id {
+            TypeId::Interface(interface_id) => self
+                .objects()
+                .filter(|(_, obj)| obj.implements_interfaces.contains(&interface_id))
+                .map(|(id, _)| TypeId::Object(id))
+                .collect(),
+            TypeId::Union(union_id) => self.get_union(union_id).variants.clone(),
+            other => vec![other],
+        }
+    }
+
     pub(crate) fn inputs(&self) -> impl Iterator<Item = (InputId, &StoredInputType)> {
         self.stored_inputs
             .iter()
@@ -380,6 +491,31 @@ impl Schema {
 }
 
 impl StoredInputType {
+    /// `Some(None)` should be interpreted as "deprecated, without reason"
+    pub(crate) fn field_deprecation(&self, field: &str) -> Option<Option<&str>> {
+        self.deprecated_fields
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, reason)| reason.as_deref())
+    }
+
+    /// The fields that must be given a value: the non-null fields without a default value.
+    pub(crate) fn required_fields(&self) -> impl Iterator<Item = &str> {
+        self.fields
+            .iter()
+            .filter(move |(name, field_type)| {
+                !field_type.is_optional() && !self.defaulted_fields.contains(name)
+            })
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub(crate) fn field_description(&self, field: &str) -> Option<&str> {
+        self.field_descriptions
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, description)| description.as_str())
+    }
+
     pub(crate) fn used_input_ids_recursive(&self, used_types: &mut UsedTypes, schema: &Schema) {
         for type_id in self.fields.iter().map(|(_name, ty)| ty.id) {
             match type_id {
@@ -427,6 +563,60 @@ impl StoredInputType {
     }
 }
 
+/// A deprecated enum value or input object field used in a literal value.
+pub(crate) struct DeprecatedLiteral<'schema> {
+    /// What is deprecated, e.g. "enum value `Role.EDITOR`".
+    pub(crate) item: String,
+    pub(crate) reason: Option<&'schema str>,
+}
+
+/// Collect the deprecated enum values and input object fields used in `value`, a literal of the
+/// type `type_id`, e.g. an argument or the default value of a variable.
+pub(crate) fn collect_deprecated_literals<'schema>(
+    value: &graphql_parser::query::Value,
+    type_id: TypeId,
+    schema: &'schema Schema,
+    literals: &mut Vec<DeprecatedLiteral<'schema>>,
+) {
+    use graphql_parser::query::Value;
+
+    match (value, type_id) {
+        (Value::Enum(variant), TypeId::Enum(enum_id)) => {
+            let r#enum = schema.get_enum(enum_id);
+
+            if let Some(reason) = r#enum.variant_deprecation(variant) {
+                literals.push(DeprecatedLiteral {
+                    item: format!("enum value `{}.{}`", r#enum.name, variant),
+                    reason,
+                });
+            }
+        }
+        (Value::Object(fields), TypeId::Input(input_id)) => {
+            let input = schema.get_input(input_id);
+
+            for (name, value) in fields {
+                if let Some(reason) = input.field_deprecation(name) {
+                    literals.push(DeprecatedLiteral {
+                        item: format!("input field `{}.{}`", input.name, name),
+                        reason,
+                    });
+                }
+
+                if let Some((_, field_type)) = input.fields.iter().find(|(field, _)| field == name)
+                {
+                    collect_deprecated_literals(value, field_type.id, schema, literals);
+                }
+            }
+        }
+        (Value::List(items), _) => {
+            for item in items {
+                collect_deprecated_literals(item, type_id, schema, literals);
+            }
+        }
+        _ => (),
+    }
+}
+
 pub(crate) fn input_is_recursive_without_indirection(input_id: InputId, schema: &Schema) -> bool {
     let input = schema.get_input(input_id);
     input.contains_type_without_indirection(input_id, schema)
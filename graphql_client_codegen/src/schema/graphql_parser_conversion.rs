@@ -137,6 +137,7 @@ fn populate_names_map(schema: &mut Schema, definitions: &[Definition]) {
 fn ingest_union(schema: &mut Schema, union: &mut UnionType) {
     let stored_union = super::StoredUnion {
         name: std::mem::take(&mut union.name),
+        description: union.description.take(),
         variants: union
             .types
             .iter()
@@ -157,6 +158,8 @@ fn ingest_object(schema: &mut Schema, obj: &mut graphql_parser::schema::ObjectTy
             r#type: resolve_field_type(schema, &field.field_type),
             parent: super::StoredFieldParent::Object(object_id),
             deprecation: find_deprecation(&field.directives),
+            arguments: ingest_arguments(schema, &mut field.arguments),
+            description: field.description.take(),
         };
 
         field_ids.push(schema.push_field(field));
@@ -165,6 +168,7 @@ fn ingest_object(schema: &mut Schema, obj: &mut graphql_parser::schema::ObjectTy
     // Ingest the object itself
     let object = super::StoredObject {
         name: std::mem::take(&mut obj.name),
+        description: obj.description.take(),
         fields: field_ids,
         implements_interfaces: obj
             .implements_interfaces
@@ -180,7 +184,10 @@ fn ingest_scalar(schema: &mut Schema, scalar: &mut graphql_parser::schema::Scala
     let name = std::mem::take(&mut scalar.name);
     let name_for_names = name.clone();
 
-    let scalar = super::StoredScalar { name };
+    let scalar = super::StoredScalar {
+        name,
+        description: scalar.description.take(),
+    };
 
     let scalar_id = schema.push_scalar(scalar);
 
@@ -192,6 +199,22 @@ fn ingest_scalar(schema: &mut Schema, scalar: &mut graphql_parser::schema::Scala
 fn ingest_enum(schema: &mut Schema, enm: &mut graphql_parser::schema::EnumType) {
     let enm = super::StoredEnum {
         name: std::mem::take(&mut enm.name),
+        description: enm.description.take(),
+        variant_descriptions: enm
+            .values
+            .iter_mut()
+            .filter_map(|value| {
+                let description = value.description.take()?;
+                Some((value.name.clone(), description))
+            })
+            .collect(),
+        deprecated_variants: enm
+            .values
+            .iter()
+            .filter_map(|value| {
+                find_deprecation(&value.directives).map(|reason| (value.name.clone(), reason))
+            })
+            .collect(),
         variants: enm
             .values
             .iter_mut()
@@ -216,6 +239,8 @@ fn ingest_interface(schema: &mut Schema, interface: &mut graphql_parser::schema:
             r#type: resolve_field_type(schema, &field.field_type),
             parent: super::StoredFieldParent::Interface(interface_id),
             deprecation: find_deprecation(&field.directives),
+            arguments: ingest_arguments(schema, &mut field.arguments),
+            description: field.description.take(),
         };
 
         field_ids.push(schema.push_field(field));
@@ -223,12 +248,35 @@ fn ingest_interface(schema: &mut Schema, interface: &mut graphql_parser::schema:
 
     let new_interface = super::StoredInterface {
         name: std::mem::take(&mut interface.name),
+        description: interface.description.take(),
         fields: field_ids,
     };
 
     schema.push_interface(new_interface);
 }
 
+fn ingest_arguments(
+    schema: &Schema,
+    arguments: &mut [parser::InputValue],
+) -> Vec<super::StoredFieldArgument> {
+    arguments
+        .iter_mut()
+        .map(|argument| {
+            let argument_type = resolve_field_type(schema, &argument.value_type);
+
+            super::StoredFieldArgument {
+                name: std::mem::take(&mut argument.name),
+                r#type: StoredInputFieldType {
+                    id: argument_type.id,
+                    qualifiers: argument_type.qualifiers,
+                },
+                has_default_value: argument.default_value.is_some(),
+                deprecation: find_deprecation(&argument.directives),
+            }
+        })
+        .collect()
+}
+
 fn find_deprecation(directives: &[parser::Directive]) -> Option<Option<String>> {
     directives
         .iter()
@@ -248,6 +296,28 @@ fn find_deprecation(directives: &[parser::Directive]) -> Option<Option<String>>
 fn ingest_input(schema: &mut Schema, input: &mut parser::InputObjectType) {
     let input = super::StoredInputType {
         name: std::mem::take(&mut input.name),
+        description: input.description.take(),
+        field_descriptions: input
+            .fields
+            .iter_mut()
+            .filter_map(|field| {
+                let description = field.description.take()?;
+                Some((field.name.clone(), description))
+            })
+            .collect(),
+        deprecated_fields: input
+            .fields
+            .iter()
+            .filter_map(|field| {
+                find_deprecation(&field.directives).map(|reason| (field.name.clone(), reason))
+            })
+            .collect(),
+        defaulted_fields: input
+            .fields
+            .iter()
+            .filter(|field| field.default_value.is_some())
+            .map(|field| field.name.clone())
+            .collect(),
         fields: input
             .fields
             .iter_mut()
@@ -1,6 +1,7 @@
 use super::{Schema, TypeId};
 use graphql_introspection_query::introspection_response::{
-    FullType, IntrospectionResponse, Schema as JsonSchema, TypeRef, __TypeKind,
+    __TypeKind, FullType, FullTypeFieldsArgs, InputValue, IntrospectionResponse,
+    Schema as JsonSchema, TypeRef,
 };
 
 pub(super) fn build_schema(src: IntrospectionResponse) -> Schema {
@@ -133,7 +134,10 @@ fn ingest_scalar(schema: &mut Schema, scalar: &mut FullType) {
     let name: String = scalar.name.take().expect("scalar.name");
     let names_name = name.clone();
 
-    let id = schema.push_scalar(super::StoredScalar { name });
+    let id = schema.push_scalar(super::StoredScalar {
+        name,
+        description: scalar.description.take(),
+    });
 
     schema.names.insert(names_name, TypeId::Scalar(id));
 }
@@ -142,6 +146,31 @@ fn ingest_enum(schema: &mut Schema, enm: &mut FullType) {
     let name = enm.name.take().expect("enm.name");
     let names_name = name.clone();
 
+    let deprecated_variants = enm
+        .enum_values
+        .as_ref()
+        .expect("enm.enum_values.as_ref()")
+        .iter()
+        .filter(|v| v.is_deprecated == Some(true))
+        .map(|v| {
+            (
+                v.name.clone().expect("variant.name"),
+                v.deprecation_reason.clone(),
+            )
+        })
+        .collect();
+
+    let variant_descriptions = enm
+        .enum_values
+        .as_mut()
+        .expect("enm.enum_values.as_mut()")
+        .iter_mut()
+        .filter_map(|v| {
+            let description = v.description.take()?;
+            Some((v.name.clone().expect("variant.name"), description))
+        })
+        .collect();
+
     let variants = enm
         .enum_values
         .as_mut()
@@ -157,7 +186,13 @@ fn ingest_enum(schema: &mut Schema, enm: &mut FullType) {
         })
         .collect();
 
-    let enm = super::StoredEnum { name, variants };
+    let enm = super::StoredEnum {
+        name,
+        variants,
+        deprecated_variants,
+        description: enm.description.take(),
+        variant_descriptions,
+    };
 
     let id = schema.push_enum(enm);
 
@@ -185,6 +220,8 @@ fn ingest_interface(schema: &mut Schema, iface: &mut FullType) {
             } else {
                 None
             },
+            arguments: ingest_arguments(schema, field.args.as_mut()),
+            description: field.description.take(),
         };
 
         field_ids.push(schema.push_field(field));
@@ -192,6 +229,7 @@ fn ingest_interface(schema: &mut Schema, iface: &mut FullType) {
 
     let interface = super::StoredInterface {
         name: std::mem::take(iface.name.as_mut().expect("iface.name.as_mut")),
+        description: iface.description.take(),
         fields: field_ids,
     };
 
@@ -220,6 +258,8 @@ fn ingest_object(schema: &mut Schema, object: &mut FullType) {
             } else {
                 None
             },
+            arguments: ingest_arguments(schema, field.args.as_mut()),
+            description: field.description.take(),
         };
 
         field_ids.push(schema.push_field(field));
@@ -227,6 +267,7 @@ fn ingest_object(schema: &mut Schema, object: &mut FullType) {
 
     let object = super::StoredObject {
         name: object.name.take().expect("take object name"),
+        description: object.description.take(),
         implements_interfaces: object
             .interfaces
             .as_ref()
@@ -255,6 +296,22 @@ fn ingest_object(schema: &mut Schema, object: &mut FullType) {
     schema.push_object(object);
 }
 
+fn ingest_arguments(
+    schema: &mut Schema,
+    args: Option<&mut Vec<Option<FullTypeFieldsArgs>>>,
+) -> Vec<super::StoredFieldArgument> {
+    args.into_iter()
+        .flatten()
+        .filter_map(Option::as_mut)
+        .map(|arg| super::StoredFieldArgument {
+            name: std::mem::take(&mut arg.input_value.name),
+            r#type: resolve_input_field_type(schema, &mut arg.input_value.type_),
+            has_default_value: arg.input_value.default_value.is_some(),
+            deprecation: input_value_deprecation(&arg.input_value),
+        })
+        .collect()
+}
+
 fn ingest_union(schema: &mut Schema, union: &mut FullType) {
     let variants = union
         .possible_types
@@ -273,6 +330,7 @@ fn ingest_union(schema: &mut Schema, union: &mut FullType) {
         .collect();
     let un = super::StoredUnion {
         name: union.name.take().expect("union.name.take"),
+        description: union.description.take(),
         variants,
     };
 
@@ -281,6 +339,9 @@ fn ingest_union(schema: &mut Schema, union: &mut FullType) {
 
 fn ingest_input(schema: &mut Schema, input: &mut FullType) {
     let mut fields = Vec::new();
+    let mut deprecated_fields = Vec::new();
+    let mut defaulted_fields = Vec::new();
+    let mut field_descriptions = Vec::new();
 
     for field in input
         .input_fields
@@ -288,6 +349,18 @@ fn ingest_input(schema: &mut Schema, input: &mut FullType) {
         .expect("Missing input_fields on input")
         .iter_mut()
     {
+        if let Some(reason) = input_value_deprecation(&field.input_value) {
+            deprecated_fields.push((field.input_value.name.clone(), reason));
+        }
+
+        if field.input_value.default_value.is_some() {
+            defaulted_fields.push(field.input_value.name.clone());
+        }
+
+        if let Some(description) = field.input_value.description.take() {
+            field_descriptions.push((field.input_value.name.clone(), description));
+        }
+
         fields.push((
             std::mem::take(&mut field.input_value.name),
             resolve_input_field_type(schema, &mut field.input_value.type_),
@@ -296,12 +369,24 @@ fn ingest_input(schema: &mut Schema, input: &mut FullType) {
 
     let input = super::StoredInputType {
         fields,
+        deprecated_fields,
+        defaulted_fields,
+        field_descriptions,
         name: input.name.take().expect("Input without a name"),
+        description: input.description.take(),
     };
 
     schema.stored_inputs.push(input);
 }
 
+fn input_value_deprecation(input_value: &InputValue) -> Option<Option<String>> {
+    if let Some(true) = input_value.is_deprecated {
+        Some(input_value.deprecation_reason.clone())
+    } else {
+        None
+    }
+}
+
 fn resolve_field_type(schema: &mut Schema, typeref: &mut TypeRef) -> super::StoredFieldType {
     from_json_type_inner(schema, typeref)
 }
@@ -9,7 +9,7 @@ fn schema_with_keywords_works() {
     let schema = Schema::from(schema);
 
     let options = GraphQLClientCodegenOptions::new(CodegenMode::Cli);
-    let query = crate::query::resolve(&schema, &query).unwrap();
+    let query = crate::query::resolve(&schema, &query, "KeywordsQuery").unwrap();
 
     for (_id, operation) in query.operations() {
         let generated_tokens = generated_module::GeneratedModule {
@@ -37,3 +37,328 @@ fn schema_with_keywords_works() {
         };
     }
 }
+
+#[test]
+fn unmapped_custom_scalars_are_listed() {
+    use crate::{generated_module, schema::Schema, CodegenMode, GraphQLClientCodegenOptions};
+
+    let query_string = "query Q { createdAt homepage ip }";
+    let query = graphql_parser::parse_query(query_string).unwrap();
+    let schema = graphql_parser::parse_schema(
+        "scalar DateTime scalar URI scalar IP type Query { createdAt: DateTime homepage: URI ip: IP }",
+    )
+    .unwrap();
+    let schema = Schema::from(schema);
+    let query = crate::query::resolve(&schema, &query, "Q").unwrap();
+
+    let mut options = GraphQLClientCodegenOptions::new(CodegenMode::Cli);
+    options.set_custom_scalar(
+        "DateTime".to_owned(),
+        "chrono::DateTime<chrono::Utc>".to_owned(),
+    );
+
+    let error = generated_module::GeneratedModule {
+        query_string,
+        schema: &schema,
+        operation: "Q",
+        resolved_query: &query,
+        options: &options,
+    }
+    .to_token_stream()
+    .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .starts_with("The query uses custom scalars without a Rust type: `IP`, `URI`."));
+}
+
+#[test]
+fn deprecated_fields_and_arguments_are_denied() {
+    use crate::{query::BoundQuery, schema::Schema};
+
+    let query_string = "query Q($first: Int) { users(first: $first, limit: 10) { name login } }";
+    let query = graphql_parser::parse_query(query_string).unwrap();
+    let schema = graphql_parser::parse_schema(
+        r#"
+        type User { name: String login: String @deprecated }
+        type Query { users(first: Int, limit: Int @deprecated(reason: "Use first")): [User] }
+        "#,
+    )
+    .unwrap();
+    let schema = Schema::from(schema);
+    let query = crate::query::resolve(&schema, &query, "Q").unwrap();
+    let bound_query = BoundQuery {
+        query: &query,
+        schema: &schema,
+    };
+
+    let error =
+        crate::query::validate_deprecations(query.operations().map(|(id, _)| id), &bound_query)
+            .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "line 1, column 24: The deprecated argument `limit` of `Query.users` is used at `Q.users`: Use first\n\
+         line 1, column 63: The deprecated field `User.login` is selected at `Q.users.login`: no reason given"
+    );
+}
+
+#[test]
+fn deprecated_enum_values_and_input_fields_are_denied() {
+    use crate::{query::BoundQuery, schema::Schema};
+
+    let query_string = "query Q($filter: UserFilter = { legacyId: \"abcd\" }) {
+  users(filter: $filter, role: EDITOR) { name }
+}";
+    let query = graphql_parser::parse_query(query_string).unwrap();
+    let schema = graphql_parser::parse_schema(
+        r#"
+        type User { name: String }
+        enum Role { ADMIN EDITOR @deprecated(reason: "Use ADMIN") VIEWER }
+        input UserFilter { name: String legacyId: String @deprecated }
+        type Query { users(filter: UserFilter, role: Role): [User] }
+        "#,
+    )
+    .unwrap();
+    let schema = Schema::from(schema);
+    let query = crate::query::resolve(&schema, &query, "Q").unwrap();
+    let bound_query = BoundQuery {
+        query: &query,
+        schema: &schema,
+    };
+
+    let error =
+        crate::query::validate_deprecations(query.operations().map(|(id, _)| id), &bound_query)
+            .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 3: The deprecated enum value `Role.EDITOR` is used at `Q.users`: Use ADMIN\n\
+         line 1, column 9: The deprecated input field `UserFilter.legacyId` is used in the default value of `$filter` in `Q`: no reason given"
+    );
+}
+
+#[test]
+fn descriptions_are_doc_comments() {
+    use crate::{generated_module, schema::Schema, CodegenMode, GraphQLClientCodegenOptions};
+
+    let query_string = "query Q($filter: UserFilter) { users(filter: $filter) { name role } }";
+    let query = graphql_parser::parse_query(query_string).unwrap();
+    let schema = graphql_parser::parse_schema(
+        r#"
+        "A person with an account."
+        type User {
+          "The display name."
+          name: String
+          role: Role
+        }
+
+        """
+        What a user can do.
+        """
+        enum Role {
+          "Can do anything."
+          ADMIN
+          VIEWER
+        }
+
+        "Which users to return."
+        input UserFilter {
+          "Part of the \"name\"."
+          name: String
+        }
+
+        type Query { users(filter: UserFilter): [User] }
+        "#,
+    )
+    .unwrap();
+    let schema = Schema::from(schema);
+    let query = crate::query::resolve(&schema, &query, "Q").unwrap();
+    let options = GraphQLClientCodegenOptions::new(CodegenMode::Cli);
+
+    let generated_code = generated_module::GeneratedModule {
+        query_string,
+        schema: &schema,
+        operation: "Q",
+        resolved_query: &query,
+        options: &options,
+    }
+    .to_token_stream()
+    .unwrap()
+    .to_string();
+
+    for expected in &[
+        r#"# [doc = " A person with an account."] # [derive (Deserialize)] pub struct QUsers"#,
+        r#"# [doc = " The display name."] pub name"#,
+        r#"# [doc = " What a user can do."] # [derive ()] pub enum Role"#,
+        r#"# [doc = " Can do anything."] ADMIN , VIEWER ,"#,
+        r#"# [doc = " Which users to return."] # [derive (Serialize)] pub struct UserFilter"#,
+        r#"# [doc = " Part of the \"name\"."] pub name"#,
+    ] {
+        assert!(
+            generated_code.contains(expected),
+            "`{}` is not in:\n{}",
+            expected,
+            generated_code
+        );
+    }
+}
+
+#[test]
+fn fields_selected_directly_and_through_a_fragment_are_merged() {
+    use crate::{generated_module, schema::Schema, CodegenMode, GraphQLClientCodegenOptions};
+
+    let query_string = "
+        query Q { human { starships { id } ...HumanStarships } }
+        fragment HumanStarships on Human { name starships { name pilot { name } } }
+    ";
+    let query = graphql_parser::parse_query(query_string).unwrap();
+    let schema = graphql_parser::parse_schema(
+        r#"
+        type Starship { id: ID! name: String! pilot: Human }
+        type Human { name: String! starships: [Starship!]! }
+        type Query { human: Human }
+        "#,
+    )
+    .unwrap();
+    let schema = Schema::from(schema);
+    let query = crate::query::resolve(&schema, &query, "Q").unwrap();
+    let options = GraphQLClientCodegenOptions::new(CodegenMode::Cli);
+
+    let generated_code = generated_module::GeneratedModule {
+        query_string,
+        schema: &schema,
+        operation: "Q",
+        resolved_query: &query,
+        options: &options,
+    }
+    .to_token_stream()
+    .unwrap()
+    .to_string();
+
+    // The fragment is expanded, so the parent struct has one field with both subselections.
+    assert!(generated_code.contains(
+        "pub struct QHuman { pub starships : Vec < QHumanStarships > , pub name : String , }"
+    ));
+    assert!(generated_code.contains(
+        "pub struct QHumanStarships { pub id : ID , pub name : String , pub pilot : Option < QHumanStarshipsPilot > , }"
+    ));
+    assert!(generated_code.contains("pub struct QHumanStarshipsPilot { pub name : String , }"));
+}
+
+#[test]
+fn schema_errors_name_the_schema_file() {
+    use crate::{generate_module_token_stream, CodegenMode, GraphQLClientCodegenOptions};
+
+    let tests_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests");
+
+    for (schema_file, expected) in &[
+        (
+            "broken_schema.graphql",
+            "broken_schema.graphql: schema parse error: Parse error at 3:13",
+        ),
+        (
+            "broken_schema.json",
+            "broken_schema.json:4:17: expected value at line 4 column 17",
+        ),
+    ] {
+        let schema_path = tests_dir.join(schema_file);
+        let error = generate_module_token_stream(
+            tests_dir.join("star_wars_query.graphql"),
+            &schema_path,
+            GraphQLClientCodegenOptions::new(CodegenMode::Cli),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(
+            error.starts_with(&format!("Schema parser error in {}", schema_path.display())),
+            "{}",
+            error
+        );
+        assert!(error.contains(expected), "{}", error);
+    }
+}
+
+#[test]
+fn out_of_range_integer_defaults_are_rejected() {
+    use crate::{generated_module, schema::Schema, CodegenMode, GraphQLClientCodegenOptions};
+
+    let schema = graphql_parser::parse_schema(
+        "scalar Level scalar Ratio type Query { users(first: Int, level: Level, ratio: Ratio): [String] }",
+    )
+    .unwrap();
+    let schema = Schema::from(schema);
+
+    let mut options = GraphQLClientCodegenOptions::new(CodegenMode::Cli);
+    options.set_custom_scalar("Level".to_owned(), "u8".to_owned());
+    options.set_custom_scalar("Ratio".to_owned(), "f32".to_owned());
+
+    let generate = |query_string: &str| {
+        let query = graphql_parser::parse_query(query_string).unwrap();
+        let query = crate::query::resolve(&schema, &query, "Q").unwrap();
+
+        generated_module::GeneratedModule {
+            query_string,
+            schema: &schema,
+            operation: "Q",
+            resolved_query: &query,
+            options: &options,
+        }
+        .to_token_stream()
+        .map(|tokens| tokens.to_string())
+        .map_err(|err| err.to_string())
+    };
+
+    assert_eq!(
+        generate("query Q($first: Int = 3000000000) { users(first: $first) }").unwrap_err(),
+        "The default value `3000000000` is out of range for the `Int` scalar: `Int` is a signed 32-bit integer."
+    );
+    assert_eq!(
+        generate("query Q($level: Level = 300) { users(level: $level) }").unwrap_err(),
+        "The default value `300` is out of range for the `Level` scalar: it does not fit in its Rust type, `u8`."
+    );
+    assert!(
+        generate("query Q($ratio: Ratio = 1) { users(ratio: $ratio) }")
+            .unwrap()
+            .contains("Some (1.0)")
+    );
+}
+
+#[test]
+fn numeric_defaults_are_converted_into_newtype_scalars() {
+    use crate::{generated_module, schema::Schema, CodegenMode, GraphQLClientCodegenOptions};
+
+    let schema = graphql_parser::parse_schema(
+        "scalar Level type Query { users(id: ID, first: Int, ratio: Float, level: Level): [String] }",
+    )
+    .unwrap();
+    let schema = Schema::from(schema);
+
+    let mut options = GraphQLClientCodegenOptions::new(CodegenMode::Cli);
+    options.set_custom_scalar("ID".to_owned(), "crate::Id".to_owned());
+    options.set_custom_scalar("Int".to_owned(), "crate::Count".to_owned());
+    options.set_custom_scalar("Float".to_owned(), "crate::Decimal".to_owned());
+
+    let query_string =
+        "query Q($id: ID = 5, $first: Int = 10, $ratio: Float = 1, $level: Level = 3) { users(id: $id, first: $first, ratio: $ratio, level: $level) }";
+    let query = graphql_parser::parse_query(query_string).unwrap();
+    let query = crate::query::resolve(&schema, &query, "Q").unwrap();
+
+    let generated = generated_module::GeneratedModule {
+        query_string,
+        schema: &schema,
+        operation: "Q",
+        resolved_query: &query,
+        options: &options,
+    }
+    .to_token_stream()
+    .unwrap()
+    .to_string();
+
+    assert!(generated.contains("Some (5i64 . into ())"));
+    assert!(generated.contains("Some (10i64 . into ())"));
+    assert!(generated.contains("Some (1f64 . into ())"));
+    // Unmapped custom scalars keep plain literals.
+    assert!(generated.contains("Some (3)"));
+}
@@ -1,59 +1,392 @@
-use crate::{BoxError, GeneralError};
 use graphql_client_codegen::deprecation::DeprecationStrategy;
 use graphql_client_codegen::normalization::Normalization;
+use graphql_client_codegen::scalar_presets::ScalarPreset;
 
 const DEPRECATION_ERROR: &str = "deprecated must be one of 'allow', 'deny', or 'warn'";
 const NORMALIZATION_ERROR: &str = "normalization must be one of 'none' or 'rust'";
 
+/// The keys accepted in the `graphql` attribute.
+const KEYS: &[&str] = &[
+    "schema_path",
+    "query_path",
+    "variables_derives",
+    "response_derives",
+    "deprecated",
+    "normalization",
+    "scalars",
+    "scalar_presets",
+    "extern_enums",
+    "closed_enums",
+];
+
 /// The `graphql` attribute as a `syn::Path`.
 fn path_to_match() -> syn::Path {
     syn::parse_str("graphql").expect("`graphql` is a valid path")
 }
 
-/// Extract an configuration parameter specified in the `graphql` attribute.
-pub fn extract_attr(ast: &syn::DeriveInput, attr: &str) -> Result<String, syn::Error> {
-    let attributes = &ast.attrs;
+/// Find the `graphql` attribute on the struct under derive.
+pub fn graphql_attribute(ast: &syn::DeriveInput) -> Result<&syn::Attribute, syn::Error> {
     let graphql_path = path_to_match();
-    let attribute = attributes
+
+    ast.attrs
         .iter()
         .find(|attr| attr.path == graphql_path)
-        .ok_or_else(|| syn::Error::new_spanned(ast, "The graphql attribute is missing"))?;
-    if let syn::Meta::List(items) = &attribute.parse_meta().expect("Attribute is well formatted") {
-        for item in items.nested.iter() {
-            if let syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) = item {
-                let syn::MetaNameValue { path, lit, .. } = name_value;
-                if let Some(ident) = path.get_ident() {
-                    if ident == attr {
-                        if let syn::Lit::Str(lit) = lit {
-                            return Ok(lit.value());
-                        }
-                    }
+        .ok_or_else(|| syn::Error::new_spanned(&ast.ident, "The graphql attribute is missing"))
+}
+
+/// The configuration in the `graphql` attribute of the struct under derive.
+#[derive(Debug)]
+pub struct GraphQLAttribute {
+    pub schema_path: String,
+    pub query_path: String,
+    pub variables_derives: Option<String>,
+    pub response_derives: Option<String>,
+    pub deprecation_strategy: Option<DeprecationStrategy>,
+    pub normalization: Option<Normalization>,
+    /// The Rust types of custom scalars, by GraphQL name.
+    pub scalars: Vec<(String, String)>,
+    /// The presets mapping common custom scalars to Rust types.
+    pub scalar_presets: Vec<ScalarPreset>,
+    /// The enums defined next to the struct instead of generated, by GraphQL name.
+    pub extern_enums: Vec<String>,
+    /// The enums generated without the `Other(String)` variant, by GraphQL name.
+    pub closed_enums: ClosedEnums,
+}
+
+/// The value of `closed_enums`: `closed_enums = "all"` or `closed_enums("Episode", ...)`.
+#[derive(Debug, PartialEq)]
+pub enum ClosedEnums {
+    All,
+    Some(Vec<String>),
+}
+
+impl GraphQLAttribute {
+    /// Parse the `graphql` attribute. Unknown keys, keys set more than once and invalid values are
+    /// errors, reported on the offending key or value. All the errors are reported together.
+    pub fn parse(ast: &syn::DeriveInput) -> Result<Self, syn::Error> {
+        let attribute = graphql_attribute(ast)?;
+        let mut errors = Errors::default();
+        let mut keys: Vec<syn::Ident> = Vec::new();
+        let mut values: Vec<(syn::Ident, syn::LitStr)> = Vec::new();
+        let mut scalars = Vec::new();
+        let mut scalar_presets = Vec::new();
+        let mut extern_enums = Vec::new();
+        let mut closed_enums = Vec::new();
+
+        let items = match attribute.parse_meta()? {
+            syn::Meta::List(list) => list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected a list of options, e.g. #[graphql(schema_path = \"...\", query_path = \"...\")]",
+                ))
+            }
+        };
+
+        for item in items {
+            let meta = match item {
+                syn::NestedMeta::Meta(meta) => meta,
+                syn::NestedMeta::Lit(lit) => {
+                    errors.push(syn::Error::new_spanned(
+                        lit,
+                        "Expected an option of the form `key = \"value\"`",
+                    ));
+                    continue;
                 }
+            };
+
+            let key = match meta.path().get_ident() {
+                Some(key) if KEYS.contains(&key.to_string().as_str()) => key.clone(),
+                _ => {
+                    errors.push(unknown_key_error(meta.path()));
+                    continue;
+                }
+            };
+
+            if keys.contains(&key) {
+                errors.push(syn::Error::new_spanned(
+                    &key,
+                    format!("`{}` is set more than once in the graphql attribute", key),
+                ));
+                continue;
+            }
+
+            keys.push(key.clone());
+
+            match (key.to_string().as_str(), meta) {
+                ("scalars", syn::Meta::List(list)) => {
+                    parse_scalars(list, &mut scalars, &mut errors)
+                }
+                ("scalars", meta) => errors.push(syn::Error::new_spanned(
+                    meta,
+                    "Expected `scalars(Name = \"path::to::Type\", ...)`",
+                )),
+                ("scalar_presets", syn::Meta::List(list)) => {
+                    parse_scalar_presets(list, &mut scalar_presets, &mut errors)
+                }
+                ("scalar_presets", meta) => errors.push(syn::Error::new_spanned(
+                    meta,
+                    "Expected `scalar_presets(\"chrono\", ...)`",
+                )),
+                ("extern_enums", syn::Meta::List(list)) => {
+                    parse_enum_names(list, &mut extern_enums, &mut errors)
+                }
+                ("extern_enums", meta) => errors.push(syn::Error::new_spanned(
+                    meta,
+                    "Expected `extern_enums(\"Name\", ...)`",
+                )),
+                ("closed_enums", syn::Meta::List(list)) => {
+                    parse_enum_names(list, &mut closed_enums, &mut errors)
+                }
+                (_, syn::Meta::NameValue(name_value)) => match name_value.lit {
+                    syn::Lit::Str(lit) => values.push((key, lit)),
+                    lit => errors.push(syn::Error::new_spanned(
+                        lit,
+                        format!("The value of `{}` must be a string literal", key),
+                    )),
+                },
+                (_, meta) => errors.push(syn::Error::new_spanned(
+                    meta,
+                    format!("Expected `{} = \"...\"`", key),
+                )),
             }
         }
+
+        let value = |key: &str| {
+            values
+                .iter()
+                .find(|(other_key, _)| other_key == key)
+                .map(|(_, lit)| lit)
+        };
+
+        let mut required = |key: &str| match value(key) {
+            Some(lit) => lit.value(),
+            None => {
+                errors.push(syn::Error::new_spanned(
+                    attribute,
+                    format!("Attribute `{}` not found", key),
+                ));
+                String::new()
+            }
+        };
+
+        let schema_path = required("schema_path");
+        let query_path = required("query_path");
+
+        let deprecation_strategy = parse_value(value("deprecated"), DEPRECATION_ERROR, &mut errors);
+        let normalization = parse_value(value("normalization"), NORMALIZATION_ERROR, &mut errors);
+
+        let closed_enums = match value("closed_enums") {
+            Some(lit) if lit.value() == "all" => ClosedEnums::All,
+            Some(lit) => {
+                errors.push(syn::Error::new_spanned(
+                    lit,
+                    "closed_enums must be \"all\" or a list of enums, e.g. `closed_enums(\"Episode\")`",
+                ));
+                ClosedEnums::All
+            }
+            None => ClosedEnums::Some(closed_enums),
+        };
+
+        errors.into_result()?;
+
+        Ok(GraphQLAttribute {
+            schema_path,
+            query_path,
+            variables_derives: value("variables_derives").map(syn::LitStr::value),
+            response_derives: value("response_derives").map(syn::LitStr::value),
+            deprecation_strategy,
+            normalization,
+            scalars,
+            scalar_presets,
+            extern_enums,
+            closed_enums,
+        })
     }
+}
+
+/// Parse `scalars(DateTime = "chrono::DateTime<chrono::Utc>", ...)`. The values must be Rust types.
+fn parse_scalars(list: syn::MetaList, scalars: &mut Vec<(String, String)>, errors: &mut Errors) {
+    for item in list.nested {
+        let name_value = match item {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) => name_value,
+            other => {
+                errors.push(syn::Error::new_spanned(
+                    other,
+                    "Expected a scalar mapping of the form `Name = \"path::to::Type\"`",
+                ));
+                continue;
+            }
+        };
 
-    Err(syn::Error::new_spanned(ast, "Attribute not found"))
+        let name = match name_value.path.get_ident() {
+            Some(name) => name.to_string(),
+            None => {
+                errors.push(syn::Error::new_spanned(
+                    name_value.path,
+                    "Expected the name of a GraphQL scalar",
+                ));
+                continue;
+            }
+        };
+
+        if scalars.iter().any(|(other_name, _)| *other_name == name) {
+            errors.push(syn::Error::new_spanned(
+                name_value.path,
+                format!("The `{}` scalar is mapped more than once", name),
+            ));
+            continue;
+        }
+
+        match name_value.lit {
+            syn::Lit::Str(lit) => match syn::parse_str::<syn::Type>(&lit.value()) {
+                Ok(_) => scalars.push((name, lit.value())),
+                Err(err) => errors.push(syn::Error::new_spanned(
+                    &lit,
+                    format!("`{}` is not a valid Rust type: {}", lit.value(), err),
+                )),
+            },
+            lit => errors.push(syn::Error::new_spanned(
+                lit,
+                format!(
+                    "The Rust type of the `{}` scalar must be a string literal",
+                    name
+                ),
+            )),
+        }
+    }
 }
 
-/// Get the deprecation from a struct attribute in the derive case.
-pub fn extract_deprecation_strategy(
-    ast: &syn::DeriveInput,
-) -> Result<DeprecationStrategy, BoxError> {
-    extract_attr(&ast, "deprecated")?
-        .to_lowercase()
-        .as_str()
-        .parse()
-        .map_err(|_| GeneralError(DEPRECATION_ERROR.to_owned()).into())
+/// Parse a list of scalar presets, e.g. `scalar_presets("chrono", "uuid")`.
+fn parse_scalar_presets(list: syn::MetaList, presets: &mut Vec<ScalarPreset>, errors: &mut Errors) {
+    for item in list.nested {
+        match item {
+            syn::NestedMeta::Lit(syn::Lit::Str(lit)) => match lit.value().parse() {
+                Ok(preset) if presets.contains(&preset) => errors.push(syn::Error::new_spanned(
+                    &lit,
+                    format!(
+                        "The `{}` scalar preset is listed more than once",
+                        lit.value()
+                    ),
+                )),
+                Ok(preset) => presets.push(preset),
+                Err(message) => errors.push(syn::Error::new_spanned(&lit, message)),
+            },
+            other => errors.push(syn::Error::new_spanned(
+                other,
+                format!(
+                    "Expected the name of a scalar preset as a string literal, one of: {}",
+                    ScalarPreset::NAMES.join(", ")
+                ),
+            )),
+        }
+    }
 }
 
-/// Get the deprecation from a struct attribute in the derive case.
-pub fn extract_normalization(ast: &syn::DeriveInput) -> Result<Normalization, BoxError> {
-    extract_attr(&ast, "normalization")?
-        .to_lowercase()
-        .as_str()
-        .parse()
-        .map_err(|_| GeneralError(NORMALIZATION_ERROR.to_owned()).into())
+/// Parse a list of enums, e.g. `extern_enums("Episode", ...)`. The values are the GraphQL names of
+/// the enums.
+fn parse_enum_names(list: syn::MetaList, names: &mut Vec<String>, errors: &mut Errors) {
+    for item in list.nested {
+        match item {
+            syn::NestedMeta::Lit(syn::Lit::Str(lit)) => {
+                if names.contains(&lit.value()) {
+                    errors.push(syn::Error::new_spanned(
+                        &lit,
+                        format!("The `{}` enum is listed more than once", lit.value()),
+                    ));
+                } else {
+                    names.push(lit.value());
+                }
+            }
+            other => errors.push(syn::Error::new_spanned(
+                other,
+                "Expected the name of a GraphQL enum as a string literal",
+            )),
+        }
+    }
+}
+
+/// Parse an optional value, case-insensitively. Invalid values are reported on the value.
+fn parse_value<T: std::str::FromStr>(
+    lit: Option<&syn::LitStr>,
+    message: &str,
+    errors: &mut Errors,
+) -> Option<T> {
+    let lit = lit?;
+
+    match lit.value().to_lowercase().parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            errors.push(syn::Error::new_spanned(lit, message));
+            None
+        }
+    }
+}
+
+fn unknown_key_error(path: &syn::Path) -> syn::Error {
+    let key = path
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+
+    let suggestion = KEYS
+        .iter()
+        .map(|known_key| (edit_distance(&key, known_key), known_key))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance);
+
+    let message = match suggestion {
+        Some((_, known_key)) => format!(
+            "Unknown key `{}` in the graphql attribute. Did you mean `{}`?",
+            key, known_key
+        ),
+        None => format!(
+            "Unknown key `{}` in the graphql attribute. The known keys are: {}",
+            key,
+            KEYS.join(", ")
+        ),
+    };
+
+    syn::Error::new_spanned(path, message)
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + if a_char == *b_char { 0 } else { 1 };
+            row.push(substitution.min(previous_row[j + 1] + 1).min(row[j] + 1));
+        }
+
+        previous_row = row;
+    }
+
+    previous_row[b.len()]
+}
+
+/// Errors accumulated while parsing the attribute, combined into one `syn::Error`.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn into_result(self) -> Result<(), syn::Error> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -73,7 +406,10 @@ mod test {
         ";
         let parsed = syn::parse_str(input).unwrap();
         assert_eq!(
-            extract_deprecation_strategy(&parsed).unwrap(),
+            GraphQLAttribute::parse(&parsed)
+                .unwrap()
+                .deprecation_strategy
+                .unwrap(),
             DeprecationStrategy::Warn
         );
     }
@@ -91,7 +427,10 @@ mod test {
         ";
         let parsed = syn::parse_str(input).unwrap();
         assert_eq!(
-            extract_deprecation_strategy(&parsed).unwrap(),
+            GraphQLAttribute::parse(&parsed)
+                .unwrap()
+                .deprecation_strategy
+                .unwrap(),
             DeprecationStrategy::Deny
         );
     }
@@ -108,9 +447,191 @@ mod test {
         struct MyQuery;
         ";
         let parsed = syn::parse_str(input).unwrap();
-        match extract_deprecation_strategy(&parsed) {
+        match GraphQLAttribute::parse(&parsed) {
             Ok(_) => panic!("parsed unexpectedly"),
             Err(e) => assert_eq!(&format!("{}", e), DEPRECATION_ERROR),
         };
     }
+
+    #[test]
+    fn test_missing_attribute() {
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\")]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        match GraphQLAttribute::parse(&parsed) {
+            Ok(_) => panic!("parsed unexpectedly"),
+            Err(e) => assert_eq!(&format!("{}", e), "Attribute `query_path` not found"),
+        };
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(
+            schema_path = \"x\",
+            query_path = \"x\",
+            variable_derives = \"Deserialize\",
+        )]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        match GraphQLAttribute::parse(&parsed) {
+            Ok(_) => panic!("parsed unexpectedly"),
+            Err(e) => assert_eq!(
+                &format!("{}", e),
+                "Unknown key `variable_derives` in the graphql attribute. Did you mean `variables_derives`?"
+            ),
+        };
+    }
+
+    #[test]
+    fn test_duplicate_and_non_string_values() {
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(
+            schema_path = \"x\",
+            query_path = \"x\",
+            query_path = \"y\",
+            response_derives = true,
+        )]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        let errors: Vec<String> = match GraphQLAttribute::parse(&parsed) {
+            Ok(_) => panic!("parsed unexpectedly"),
+            Err(e) => e.into_iter().map(|e| e.to_string()).collect(),
+        };
+        assert_eq!(
+            errors,
+            vec![
+                "`query_path` is set more than once in the graphql attribute",
+                "The value of `response_derives` must be a string literal",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scalars() {
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(
+            schema_path = \"x\",
+            query_path = \"x\",
+            scalars(DateTime = \"chrono::DateTime<chrono::Utc>\", URI = \"url::Url\"),
+        )]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert_eq!(
+            GraphQLAttribute::parse(&parsed).unwrap().scalars,
+            vec![
+                (
+                    "DateTime".to_owned(),
+                    "chrono::DateTime<chrono::Utc>".to_owned()
+                ),
+                ("URI".to_owned(), "url::Url".to_owned()),
+            ]
+        );
+
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", scalars(URI = \"url::\"))]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert!(GraphQLAttribute::parse(&parsed)
+            .unwrap_err()
+            .to_string()
+            .starts_with("`url::` is not a valid Rust type"));
+    }
+
+    #[test]
+    fn test_scalar_presets() {
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", scalar_presets(\"chrono\", \"json\"))]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert_eq!(
+            GraphQLAttribute::parse(&parsed).unwrap().scalar_presets,
+            vec![ScalarPreset::Chrono, ScalarPreset::Json]
+        );
+
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", scalar_presets(\"time\"))]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert_eq!(
+            GraphQLAttribute::parse(&parsed).unwrap_err().to_string(),
+            "Unknown scalar preset `time`. The presets are: chrono, uuid, url, json, decimal"
+        );
+    }
+
+    #[test]
+    fn test_closed_enums() {
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", closed_enums = \"all\")]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert_eq!(
+            GraphQLAttribute::parse(&parsed).unwrap().closed_enums,
+            ClosedEnums::All
+        );
+
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", closed_enums(\"Episode\"))]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert_eq!(
+            GraphQLAttribute::parse(&parsed).unwrap().closed_enums,
+            ClosedEnums::Some(vec!["Episode".to_owned()])
+        );
+
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", closed_enums = \"Episode\")]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert!(GraphQLAttribute::parse(&parsed)
+            .unwrap_err()
+            .to_string()
+            .starts_with("closed_enums must be \"all\" or a list of enums"));
+    }
+
+    #[test]
+    fn test_extern_enums() {
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", extern_enums(\"Episode\", \"State\"))]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert_eq!(
+            GraphQLAttribute::parse(&parsed).unwrap().extern_enums,
+            vec!["Episode".to_owned(), "State".to_owned()]
+        );
+
+        let input = "
+        #[derive(GraphQLQuery)]
+        #[graphql(schema_path = \"x\", query_path = \"x\", extern_enums(Episode))]
+        struct MyQuery;
+        ";
+        let parsed = syn::parse_str(input).unwrap();
+        assert_eq!(
+            GraphQLAttribute::parse(&parsed).unwrap_err().to_string(),
+            "Expected the name of a GraphQL enum as a string literal"
+        );
+    }
 }